@@ -0,0 +1,486 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use futures::{stream, Stream, StreamExt};
+use reqwest::Method;
+
+use crate::{
+    cost::CostDollars,
+    json_body,
+    search::SearchResult,
+    sse::{SseDecoder, SseEvent},
+    Exa, ExaError,
+};
+
+impl Exa {
+    /// Generates an answer to a query, grounded on Exa search results.
+    ///
+    /// This method sends a POST request to the Exa API's answer endpoint. The response
+    /// contains the generated answer along with the search results used as citations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use exa_sdk::{Exa, AnswerRequest};
+    /// # use anyhow::Result;
+    /// #
+    /// # async fn example() -> Result<()> {
+    /// # let exa = Exa::builder().api_key("your_api_key".into()).build()?;
+    /// let request = AnswerRequest::new("What is the latest stable Rust version?");
+    ///
+    /// let response = exa.answer(request).await?;
+    /// println!("{} ({} citations)", response.answer, response.citations.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn answer(&self, request: AnswerRequest) -> Result<AnswerResponse, ExaError> {
+        self.post("/answer", request).await
+    }
+
+    /// Generates an answer to a query, streaming it back as it is produced.
+    ///
+    /// The request is sent with `stream: true` and the server-sent events are decoded
+    /// incrementally, yielding content deltas and citations as soon as they arrive. Once the
+    /// whole response has been read, the stream ends with a single `AnswerChunk::Done`.
+    ///
+    /// Errors returned by the API before streaming starts are reported by the outer `Result`;
+    /// failures happening mid-stream are yielded as stream items. An event that can't be
+    /// decoded is skipped after its error, but if the connection fails, its error is the last
+    /// item and no `AnswerChunk::Done` follows.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use exa_sdk::{AnswerChunk, AnswerRequest, Exa};
+    /// # use futures::StreamExt;
+    /// # use anyhow::Result;
+    /// #
+    /// # async fn example() -> Result<()> {
+    /// # let exa = Exa::builder().api_key("your_api_key".into()).build()?;
+    /// let mut stream = Box::pin(exa.answer_stream(AnswerRequest::new("What is Rust?")).await?);
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     if let AnswerChunk::Content(delta) = chunk? {
+    ///         print!("{delta}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn answer_stream(
+        &self,
+        request: AnswerRequest,
+    ) -> Result<impl Stream<Item = Result<AnswerChunk, ExaError>>, ExaError> {
+        let request = StreamingAnswerRequest {
+            request: &request,
+            stream: true,
+        };
+        let response = self
+            .send(Method::POST, "/answer", Some(json_body(&request)?), true)
+            .await?;
+
+        let tracker = self.inner.cost_tracker.clone();
+        let tag = self.inner.cost_tag.clone();
+        Ok(answer_chunks(response.body).inspect(move |chunk| {
+            if let (
+                Some(tracker),
+                Ok(AnswerChunk::Done {
+                    cost_dollars: Some(cost),
+                }),
+            ) = (&tracker, chunk)
+            {
+                tracker.record("/answer", tag.as_deref(), cost);
+            }
+        }))
+    }
+}
+
+/// Represents an answer request to the Exa API
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct AnswerRequest {
+    /// The question to answer
+    pub query: String,
+    /// Whether to include the full text of each citation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<bool>,
+    /// The model used to generate the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<AnswerModel>,
+}
+
+impl AnswerRequest {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnswerModel {
+    Exa,
+    ExaPro,
+}
+
+/// Represents the response from an answer request to the Exa API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct AnswerResponse {
+    /// The generated answer
+    pub answer: String,
+    /// Search results the answer is grounded on
+    #[serde(default)]
+    pub citations: Vec<SearchResult>,
+    /// Identifier of the request, useful when contacting support
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// Cost of the request
+    #[serde(rename = "costDollars")]
+    pub cost_dollars: Option<CostDollars>,
+}
+
+/// A piece of an answer streamed by `Exa::answer_stream`
+#[derive(Debug)]
+pub enum AnswerChunk {
+    /// The next fragment of the answer text
+    Content(String),
+    /// Search results the answer is grounded on
+    Citations(Vec<SearchResult>),
+    /// The answer is complete
+    Done {
+        /// Cost of the request, if reported
+        cost_dollars: Option<CostDollars>,
+    },
+}
+
+#[derive(serde::Serialize)]
+struct StreamingAnswerRequest<'a> {
+    #[serde(flatten)]
+    request: &'a AnswerRequest,
+    stream: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct RawAnswerChunk {
+    #[serde(default)]
+    choices: Vec<RawAnswerChoice>,
+    citations: Option<Vec<SearchResult>>,
+    #[serde(rename = "costDollars")]
+    cost_dollars: Option<CostDollars>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawAnswerChoice {
+    delta: RawAnswerDelta,
+}
+
+#[derive(serde::Deserialize)]
+struct RawAnswerDelta {
+    content: Option<String>,
+}
+
+struct AnswerStreamState<S> {
+    bytes: S,
+    decoder: SseDecoder,
+    pending: VecDeque<Result<AnswerChunk, ExaError>>,
+    cost_dollars: Option<CostDollars>,
+    finished: bool,
+}
+
+impl<S> AnswerStreamState<S> {
+    fn handle(&mut self, event: SseEvent) {
+        if self.finished {
+            return;
+        }
+        if event.data.trim() == "[DONE]" {
+            return self.finish();
+        }
+
+        let chunk = match serde_json::from_str::<RawAnswerChunk>(&event.data) {
+            Ok(chunk) => chunk,
+            Err(source) => {
+                self.pending.push_back(Err(ExaError::Decode {
+                    source,
+                    body: event.data,
+                }));
+                return;
+            }
+        };
+
+        for choice in chunk.choices {
+            if let Some(content) = choice.delta.content.filter(|content| !content.is_empty()) {
+                self.pending.push_back(Ok(AnswerChunk::Content(content)));
+            }
+        }
+        if let Some(citations) = chunk.citations {
+            self.pending
+                .push_back(Ok(AnswerChunk::Citations(citations)));
+        }
+        if chunk.cost_dollars.is_some() {
+            self.cost_dollars = chunk.cost_dollars;
+        }
+    }
+
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            self.pending.push_back(Ok(AnswerChunk::Done {
+                cost_dollars: self.cost_dollars.take(),
+            }));
+        }
+    }
+}
+
+fn answer_chunks<S, B, E>(bytes: S) -> impl Stream<Item = Result<AnswerChunk, ExaError>>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: Into<ExaError>,
+{
+    let state = AnswerStreamState {
+        bytes,
+        decoder: SseDecoder::default(),
+        pending: VecDeque::new(),
+        cost_dollars: None,
+        finished: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.finished {
+                return None;
+            }
+
+            match state.bytes.next().await {
+                Some(Ok(chunk)) => {
+                    for event in state.decoder.push(chunk.as_ref()) {
+                        state.handle(event);
+                    }
+                }
+                Some(Err(error)) => {
+                    state.finished = true;
+                    return Some((Err(error.into()), state));
+                }
+                None => {
+                    if let Some(event) = state.decoder.finish() {
+                        state.handle(event);
+                    }
+                    state.finish();
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use futures::TryStreamExt;
+    use mockito::{Matcher, Server as MockServer};
+    use serde_json::json;
+
+    use super::*;
+    use crate::transport::{TransportError, TransportErrorKind};
+
+    #[test]
+    fn test_answer_request_serialization() -> Result<()> {
+        let request = AnswerRequest {
+            query: "test query".to_string(),
+            text: Some(true),
+            model: Some(AnswerModel::ExaPro),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({"query": "test query", "text": true, "model": "exa-pro"})
+        );
+        assert_eq!(
+            serde_json::to_value(AnswerRequest::new("test query"))?,
+            json!({"query": "test query"})
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_answer() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/answer")
+            .match_body(Matcher::Json(json!({"query": "test query", "text": true})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "answer": "Test answer",
+                    "requestId": "req_123",
+                    "costDollars": {"total": 0.005},
+                    "citations": [{
+                        "id": "https://example.com",
+                        "title": "Test Title",
+                        "url": "https://example.com",
+                        "publishedDate": "2023-01-01",
+                        "author": "Test Author",
+                        "text": "Test content"
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .build()?;
+
+        let request = AnswerRequest {
+            text: Some(true),
+            ..AnswerRequest::new("test query")
+        };
+
+        let response = exa.answer(request).await?;
+
+        assert_eq!(response.answer, "Test answer");
+        assert_eq!(response.request_id, Some("req_123".to_string()));
+        assert_eq!(response.cost_dollars.map(|cost| cost.total), Some(0.005));
+        assert_eq!(response.citations.len(), 1);
+        assert_eq!(response.citations[0].url, "https://example.com");
+        assert_eq!(response.citations[0].text, Some("Test content".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_answer_error() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/answer")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "code": "bad_request",
+                    "message": "Query is required"
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .build()?;
+
+        let result = exa.answer(AnswerRequest::default()).await;
+
+        if let Err(ExaError::InvalidRequest(error)) = result {
+            assert_eq!(error.status, 400);
+            assert_eq!(error.code(), Some("bad_request"));
+        } else {
+            panic!("Expected InvalidRequest");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_answer_stream() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let body = [
+            json!({"choices": [{"delta": {"content": "Hello"}}]}),
+            json!({"choices": [{"delta": {"content": ", world"}}]}),
+            json!({"citations": [{"id": "1", "title": "Test Title", "url": "https://example.com"}]}),
+            json!({"costDollars": {"total": 0.005}}),
+        ]
+        .iter()
+        .map(|chunk| format!("data: {}\n\n", chunk))
+        .collect::<String>()
+            + "data: [DONE]\n\n";
+
+        let _m = server
+            .mock("POST", "/answer")
+            .match_body(Matcher::Json(
+                json!({"query": "test query", "stream": true}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .build()?;
+
+        let chunks = exa
+            .answer_stream(AnswerRequest::new("test query"))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(chunks.len(), 4);
+        assert!(matches!(&chunks[0], AnswerChunk::Content(content) if content == "Hello"));
+        assert!(matches!(&chunks[1], AnswerChunk::Content(content) if content == ", world"));
+        assert!(
+            matches!(&chunks[2], AnswerChunk::Citations(citations) if citations[0].url == "https://example.com")
+        );
+        assert!(matches!(
+            &chunks[3],
+            AnswerChunk::Done {
+                cost_dollars: Some(cost)
+            } if cost.total == 0.005
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_answer_stream_without_done_marker() -> Result<()> {
+        let chunks = answer_chunks(stream::iter(vec![
+            Ok::<_, ExaError>(b"data: {\"choices\": [{\"delta\"".to_vec()),
+            Ok(b": {\"content\": \"Hi\"}}]}\n\ndata: not json\n\n".to_vec()),
+        ]))
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert!(matches!(&chunks[0], Ok(AnswerChunk::Content(content)) if content == "Hi"));
+        assert!(matches!(&chunks[1], Err(ExaError::Decode { body, .. }) if body == "not json"));
+        assert!(matches!(
+            chunks[2],
+            Ok(AnswerChunk::Done { cost_dollars: None })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_answer_stream_ends_without_done_on_transport_error() -> Result<()> {
+        let chunks = answer_chunks(stream::iter(vec![
+            Ok(b"data: {\"choices\": [{\"delta\": {\"content\": \"Hi\"}}]}\n\n".to_vec()),
+            Err(TransportError::new(
+                TransportErrorKind::Body,
+                "connection reset",
+            )),
+            Ok(b"data: [DONE]\n\n".to_vec()),
+        ]))
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(chunks.len(), 2);
+        assert!(matches!(&chunks[0], Ok(AnswerChunk::Content(content)) if content == "Hi"));
+        assert!(matches!(&chunks[1], Err(ExaError::ClientError(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,375 @@
+//! A synchronous client for the Exa API, enabled by the `blocking` feature.
+//!
+//! It takes the same requests and returns the same responses as the asynchronous `Exa`, and
+//! honours the same retry policy, rate limits and cost tracker, but blocks the current thread
+//! instead of returning futures. It must not be used from within an async runtime.
+//!
+//! ```rust,ignore
+//! # use exa_sdk::{blocking::Exa, SearchRequest};
+//! # fn example() -> anyhow::Result<()> {
+//! let exa = Exa::builder().api_key("your_api_key".into()).build()?;
+//! let response = exa.search(SearchRequest {
+//!     query: "Rust programming".to_string(),
+//!     ..Default::default()
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use reqwest::blocking::{Client, Response};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    http_error, retry, transport::TransportError, ContentsRequest, ContentsResponse, CostTracker,
+    ExaError, ExaInner, FindSimilarRequest, FindSimilarResponse, HttpVersionPreference, RateLimit,
+    RetryPolicy, SearchRequest, SearchResponse,
+};
+
+/// Synchronous client for the Exa API.
+///
+/// Like the asynchronous client, cloning is cheap and clones share their connection pool, rate
+/// limiter and cost tracker.
+#[derive(Clone)]
+pub struct Exa {
+    inner: Arc<ExaInner<Client>>,
+}
+
+/// Builder for the synchronous client, with the same options as `exa_sdk::ExaBuilder`
+pub struct ExaBuilder {
+    inner: crate::ExaBuilder,
+}
+
+impl Exa {
+    pub fn builder() -> ExaBuilder {
+        ExaBuilder {
+            inner: crate::Exa::builder(),
+        }
+    }
+
+    /// The cost tracker attached to this client, if any.
+    pub fn cost_tracker(&self) -> Option<&Arc<CostTracker>> {
+        self.inner.cost_tracker.as_ref()
+    }
+
+    /// Performs a search request. See `exa_sdk::Exa::search`.
+    pub fn search(&self, request: SearchRequest) -> Result<SearchResponse, ExaError> {
+        self.post("/search", request)
+    }
+
+    /// Finds pages similar to a URL. See `exa_sdk::Exa::find_similar`.
+    pub fn find_similar(
+        &self,
+        request: FindSimilarRequest,
+    ) -> Result<FindSimilarResponse, ExaError> {
+        self.post("/findSimilar", request)
+    }
+
+    /// Fetches the contents of pages. See `exa_sdk::Exa::get_contents`.
+    pub fn get_contents(&self, request: ContentsRequest) -> Result<ContentsResponse, ExaError> {
+        self.post("/contents", request)
+    }
+
+    fn post<S, D>(&self, path: &str, request: S) -> Result<D, ExaError>
+    where
+        S: Serialize,
+        D: DeserializeOwned,
+    {
+        let response = self.send(path, &request)?;
+        let body = response.text()?;
+        self.inner.decode(path, body)
+    }
+
+    /// Sends a POST request, retrying transient failures like `exa_sdk::Exa::send`.
+    fn send<S>(&self, path: &str, request: &S) -> Result<Response, ExaError>
+    where
+        S: Serialize,
+    {
+        self.inner.check_budget()?;
+
+        let url = self.inner.url(path);
+        let retry_policy = &self.inner.retry_policy;
+        let mut attempt = 1;
+
+        loop {
+            std::thread::sleep(self.inner.rate_limiter.reserve(path));
+
+            let result = self
+                .inner
+                .client
+                .post(&url)
+                .headers(self.inner.headers())
+                .json(request)
+                .send()
+                .map_err(TransportError::from);
+
+            let delay = match &result {
+                Ok(response) => retry_policy.retry_delay(
+                    attempt,
+                    true,
+                    Ok((response.status(), response.headers())),
+                ),
+                Err(error) => retry_policy.retry_delay(attempt, true, Err(error)),
+            };
+
+            if let Some(delay) = delay {
+                std::thread::sleep(delay);
+                attempt += 1;
+                continue;
+            }
+
+            let outcome = match result {
+                Ok(response) => check_response(response),
+                Err(error) => Err(error.into()),
+            };
+
+            return outcome.map_err(|last| retry::exhausted(attempt, last));
+        }
+    }
+}
+
+fn check_response(response: Response) -> Result<Response, ExaError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let headers = response.headers().clone();
+    let body = response.text()?;
+    Err(http_error(status, &headers, body))
+}
+
+impl ExaBuilder {
+    pub fn api_key(self, api_key: String) -> Self {
+        Self {
+            inner: self.inner.api_key(api_key),
+        }
+    }
+
+    pub fn base_url(self, base_url: String) -> Self {
+        Self {
+            inner: self.inner.base_url(base_url),
+        }
+    }
+
+    /// Sets the policy used to retry transient failures. Defaults to `RetryPolicy::default()`.
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            inner: self.inner.retry_policy(retry_policy),
+        }
+    }
+
+    /// Sets how long to wait for a connection to the API to be established.
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.connect_timeout(timeout),
+        }
+    }
+
+    /// Sets how long each attempt may take in total. Defaults to 30 seconds.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.timeout(timeout),
+        }
+    }
+
+    /// Sends all requests through the proxy at `proxy`.
+    pub fn proxy(self, proxy: impl Into<String>) -> Self {
+        Self {
+            inner: self.inner.proxy(proxy),
+        }
+    }
+
+    /// Sets whether to accept gzip-compressed responses. Enabled by default.
+    pub fn gzip(self, enable: bool) -> Self {
+        Self {
+            inner: self.inner.gzip(enable),
+        }
+    }
+
+    /// Sets whether to accept brotli-compressed responses. Enabled by default.
+    pub fn brotli(self, enable: bool) -> Self {
+        Self {
+            inner: self.inner.brotli(enable),
+        }
+    }
+
+    /// Sets which HTTP version to speak with the API.
+    pub fn http_version(self, http_version: HttpVersionPreference) -> Self {
+        Self {
+            inner: self.inner.http_version(http_version),
+        }
+    }
+
+    /// Appends `suffix` to the `User-Agent` sent with every request.
+    pub fn user_agent_suffix(self, suffix: impl Into<String>) -> Self {
+        Self {
+            inner: self.inner.user_agent_suffix(suffix),
+        }
+    }
+
+    /// Limits the rate of requests sent by this client and all its clones.
+    pub fn rate_limit(self, rate_limit: RateLimit) -> Self {
+        Self {
+            inner: self.inner.rate_limit(rate_limit),
+        }
+    }
+
+    /// Limits the rate of requests to `endpoint` (e.g. `/search`), and to any path under it.
+    pub fn endpoint_rate_limit(self, endpoint: impl Into<String>, rate_limit: RateLimit) -> Self {
+        Self {
+            inner: self.inner.endpoint_rate_limit(endpoint, rate_limit),
+        }
+    }
+
+    /// Attaches a tracker adding up the dollars spent by this client, and enforcing its budget.
+    pub fn cost_tracker(self, cost_tracker: Arc<CostTracker>) -> Self {
+        Self {
+            inner: self.inner.cost_tracker(cost_tracker),
+        }
+    }
+
+    /// Sets the tag under which the cost tracker records this client's spending.
+    pub fn cost_tag(self, cost_tag: impl Into<String>) -> Self {
+        Self {
+            inner: self.inner.cost_tag(cost_tag),
+        }
+    }
+
+    pub fn build(self) -> Result<Exa> {
+        let client = self.inner.client_options.build_blocking()?;
+
+        Ok(Exa {
+            inner: Arc::new(self.inner.build_inner(client)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server as MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn setup(base_url: String) -> Result<Exa> {
+        Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(base_url)
+            .retry_policy(RetryPolicy {
+                base_delay: std::time::Duration::from_millis(1),
+                jitter: false,
+                ..Default::default()
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_blocking_search() -> Result<()> {
+        let mut server = MockServer::new();
+
+        let _m = server
+            .mock("POST", "/search")
+            .match_header("x-api-key", "test_key")
+            .match_body(Matcher::PartialJson(json!({"query": "test query"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "results": [{
+                        "id": "test_id",
+                        "url": "https://example.com",
+                        "title": "Test Title"
+                    }],
+                    "costDollars": {"total": 0.005}
+                })
+                .to_string(),
+            )
+            .create();
+
+        let tracker = Arc::new(CostTracker::new());
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .cost_tracker(tracker.clone())
+            .build()?;
+
+        let response = exa.search(SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        })?;
+
+        assert_eq!(response.results[0].id, "test_id");
+        assert_eq!(tracker.total(), 0.005);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_get_contents_retries_server_errors() -> Result<()> {
+        let mut server = MockServer::new();
+
+        let failing = server
+            .mock("POST", "/contents")
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let result = exa.get_contents(ContentsRequest::new(vec!["test_id".to_string()]));
+
+        failing.assert();
+        match result {
+            Err(ExaError::RetriesExhausted { attempts, last }) => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, ExaError::ServerError(_)));
+            }
+            other => panic!("Expected RetriesExhausted, got {:?}", other.map(|_| ())),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_find_similar_api_error() -> Result<()> {
+        let mut server = MockServer::new();
+
+        let _m = server
+            .mock("POST", "/findSimilar")
+            .with_status(401)
+            .with_body(json!({"code": "unauthorized", "message": "Invalid API key"}).to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let result = exa.find_similar(FindSimilarRequest {
+            url: "https://example.com".to_string(),
+            ..Default::default()
+        });
+
+        match result {
+            Err(ExaError::Unauthorized(error)) => assert_eq!(error.code(), Some("unauthorized")),
+            other => panic!("Expected Unauthorized, got {:?}", other.map(|_| ())),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_options_are_forwarded() -> Result<()> {
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .endpoint_rate_limit("/search", RateLimit::per_second(10.0))
+            .cost_tag("cli")
+            .build()?;
+
+        assert_eq!(exa.inner.cost_tag.as_deref(), Some("cli"));
+        assert_eq!(exa.inner.base_url, crate::BASE_URL);
+        assert!(exa.cost_tracker().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+/// Represents the cost of a request, as reported by the Exa API
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CostDollars {
+    /// Total cost of the request in dollars
+    pub total: f64,
+    /// Cost split between searching and retrieving contents
+    #[serde(rename = "breakDown", default)]
+    pub break_down: Vec<CostBreakdown>,
+    /// Price of each kind of search, per request
+    #[serde(rename = "perRequestPrices", default)]
+    pub per_request_prices: HashMap<String, f64>,
+    /// Price of each kind of content, per page
+    #[serde(rename = "perPagePrices", default)]
+    pub per_page_prices: HashMap<String, f64>,
+}
+
+impl CostDollars {
+    /// Dollars spent on searching
+    pub fn search(&self) -> f64 {
+        self.break_down.iter().filter_map(|item| item.search).sum()
+    }
+
+    /// Dollars spent on retrieving contents
+    pub fn contents(&self) -> f64 {
+        self.break_down
+            .iter()
+            .filter_map(|item| item.contents)
+            .sum()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CostBreakdown {
+    pub search: Option<f64>,
+    pub contents: Option<f64>,
+    pub breakdown: Option<CostBreakdownDetail>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CostBreakdownDetail {
+    #[serde(rename = "keywordSearch")]
+    pub keyword_search: Option<f64>,
+    #[serde(rename = "neuralSearch")]
+    pub neural_search: Option<f64>,
+    #[serde(rename = "contentText")]
+    pub content_text: Option<f64>,
+    #[serde(rename = "contentHighlight")]
+    pub content_highlight: Option<f64>,
+    #[serde(rename = "contentSummary")]
+    pub content_summary: Option<f64>,
+}
+
+/// Adds up the dollars spent by the clients it's attached to, and optionally refuses further
+/// requests once a budget has been spent.
+///
+/// A tracker is attached with `ExaBuilder::cost_tracker` and can be shared between several
+/// clients through an `Arc`, in which case they all draw from the same budget.
+///
+/// Spending is recorded from the `costDollars` reported by the API once a response arrives, and
+/// the budget is only checked before sending. Every request already in flight when the budget
+/// runs out still completes, so the total can exceed the budget by the cost of as many requests
+/// as run concurrently, e.g. up to `concurrency` searches with `Exa::search_many`.
+#[derive(Debug, Default)]
+pub struct CostTracker {
+    budget: Option<f64>,
+    summary: Mutex<CostSummary>,
+}
+
+/// Dollars spent so far, as recorded by a `CostTracker`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostSummary {
+    pub total: f64,
+    /// Number of requests that reported a cost
+    pub requests: u64,
+    /// Dollars spent per endpoint path, e.g. `/search`
+    pub by_endpoint: HashMap<String, f64>,
+    /// Dollars spent per tag set with `ExaBuilder::cost_tag`
+    pub by_tag: HashMap<String, f64>,
+}
+
+impl CostTracker {
+    /// Creates a tracker without a budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker that fails requests with `ExaError::BudgetExceeded` once `budget`
+    /// dollars have been spent.
+    pub fn with_budget(budget: f64) -> Self {
+        Self {
+            budget: Some(budget),
+            ..Default::default()
+        }
+    }
+
+    pub fn budget(&self) -> Option<f64> {
+        self.budget
+    }
+
+    /// Total dollars spent so far
+    pub fn total(&self) -> f64 {
+        self.lock().total
+    }
+
+    /// Dollars left before the budget is exhausted, if there is one
+    pub fn remaining(&self) -> Option<f64> {
+        self.budget.map(|budget| (budget - self.total()).max(0.0))
+    }
+
+    /// Returns a copy of the spending recorded so far.
+    pub fn summary(&self) -> CostSummary {
+        self.lock().clone()
+    }
+
+    /// Forgets the spending recorded so far, e.g. at the start of a new billing period.
+    pub fn reset(&self) {
+        *self.lock() = CostSummary::default();
+    }
+
+    /// Returns the dollars spent and the budget, if the budget has already been spent.
+    pub(crate) fn overspent(&self) -> Option<(f64, f64)> {
+        let budget = self.budget?;
+        let spent = self.total();
+        (spent >= budget).then_some((spent, budget))
+    }
+
+    pub(crate) fn record(&self, endpoint: &str, tag: Option<&str>, cost: &CostDollars) {
+        let mut summary = self.lock();
+        summary.total += cost.total;
+        summary.requests += 1;
+        *summary.by_endpoint.entry(endpoint.to_string()).or_default() += cost.total;
+        if let Some(tag) = tag {
+            *summary.by_tag.entry(tag.to_string()).or_default() += cost.total;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CostSummary> {
+        // The summary is only ever updated in place, so it stays consistent even if a thread
+        // panicked while holding the lock.
+        self.summary
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Extracts the cost reported in any response body
+#[derive(serde::Deserialize)]
+pub(crate) struct ReportedCost {
+    #[serde(rename = "costDollars")]
+    pub cost_dollars: Option<CostDollars>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(total: f64) -> CostDollars {
+        CostDollars {
+            total,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_costs() {
+        let tracker = CostTracker::new();
+
+        tracker.record("/search", Some("agent"), &cost(0.005));
+        tracker.record("/contents", Some("agent"), &cost(0.001));
+        tracker.record("/search", None, &cost(0.005));
+
+        let summary = tracker.summary();
+        assert_eq!(summary.requests, 3);
+        assert!((summary.total - 0.011).abs() < 1e-9);
+        assert!((summary.by_endpoint["/search"] - 0.01).abs() < 1e-9);
+        assert!((summary.by_tag["agent"] - 0.006).abs() < 1e-9);
+        assert_eq!(tracker.remaining(), None);
+
+        tracker.reset();
+        assert_eq!(tracker.summary(), CostSummary::default());
+    }
+
+    #[test]
+    fn test_budget() {
+        let tracker = CostTracker::with_budget(0.01);
+
+        assert_eq!(tracker.overspent(), None);
+        tracker.record("/search", None, &cost(0.006));
+        assert_eq!(tracker.overspent(), None);
+        assert!((tracker.remaining().unwrap() - 0.004).abs() < 1e-9);
+
+        tracker.record("/search", None, &cost(0.006));
+        assert_eq!(tracker.overspent(), Some((0.012, 0.01)));
+        assert_eq!(tracker.remaining(), Some(0.0));
+    }
+}
@@ -1,31 +1,232 @@
-use std::fmt;
+use std::{fmt, time::Duration};
+
+use crate::transport::TransportError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ExaError {
     #[error("Client error: {0}")]
-    ClientError(#[from] reqwest::Error),
+    ClientError(TransportError),
+    #[error("Request timed out: {0}")]
+    Timeout(#[source] TransportError),
+    /// The API key is missing, invalid or not allowed to perform the request (401, 403)
+    #[error("Unauthorized: {0}")]
+    Unauthorized(Box<HttpError>),
+    /// The account has run out of credits (402)
+    #[error("Insufficient credits: {0}")]
+    InsufficientCredits(Box<HttpError>),
+    /// Too many requests (429)
+    #[error("Rate limited: {error}")]
+    RateLimited {
+        /// How long the server asked to wait before retrying, if it said so
+        retry_after: Option<Duration>,
+        error: Box<HttpError>,
+    },
+    /// The request was rejected as malformed or invalid (400, 422)
+    #[error("Invalid request: {0}")]
+    InvalidRequest(Box<HttpError>),
+    /// The API failed to process a valid request (5xx)
+    #[error("Server error: {0}")]
+    ServerError(Box<HttpError>),
+    /// Any other non-success response
     #[error("HTTP error: {0}")]
-    HttpError(HttpError),
+    HttpError(Box<HttpError>),
+    /// A successful response whose body didn't match the expected shape
+    #[error("Failed to decode response: {source}")]
+    Decode {
+        #[source]
+        source: serde_json::Error,
+        /// Raw response body
+        body: String,
+    },
+    #[error("Research task {id} did not finish within {timeout:?}")]
+    ResearchTimeout { id: String, timeout: Duration },
+    /// The `CostTracker` attached to the client has spent its budget
+    #[error("Budget of ${budget} exceeded, ${spent} spent")]
+    BudgetExceeded { spent: f64, budget: f64 },
+    /// A middleware failed the request
+    #[error("Middleware error: {0}")]
+    Middleware(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Request failed after {attempts} attempts: {last}")]
+    RetriesExhausted {
+        /// Number of attempts made, including the first one
+        attempts: u32,
+        /// Error returned by the final attempt
+        #[source]
+        last: Box<ExaError>,
+    },
+}
+
+impl ExaError {
+    /// Whether the failure is transient, so the same request may succeed if sent again later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ExaError::ClientError(error) => error.is_connect() || error.is_request(),
+            ExaError::Timeout(_) | ExaError::RateLimited { .. } => true,
+            ExaError::ServerError(error) => error.status != 501,
+            ExaError::RetriesExhausted { last, .. } => last.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// The HTTP error behind this error, if the API answered with a non-success status.
+    pub fn http_error(&self) -> Option<&HttpError> {
+        match self {
+            ExaError::Unauthorized(error)
+            | ExaError::InsufficientCredits(error)
+            | ExaError::RateLimited { error, .. }
+            | ExaError::InvalidRequest(error)
+            | ExaError::ServerError(error)
+            | ExaError::HttpError(error) => Some(error),
+            ExaError::RetriesExhausted { last, .. } => last.http_error(),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code of the response, if any.
+    pub fn status(&self) -> Option<u16> {
+        self.http_error().map(|error| error.status)
+    }
+}
+
+impl From<TransportError> for ExaError {
+    fn from(error: TransportError) -> Self {
+        if error.is_timeout() {
+            ExaError::Timeout(error)
+        } else {
+            ExaError::ClientError(error)
+        }
+    }
+}
+
+impl From<reqwest::Error> for ExaError {
+    fn from(error: reqwest::Error) -> Self {
+        TransportError::from(error).into()
+    }
+}
+
+impl From<HttpError> for ExaError {
+    fn from(error: HttpError) -> Self {
+        let error = Box::new(error);
+        match error.status {
+            401 | 403 => ExaError::Unauthorized(error),
+            402 => ExaError::InsufficientCredits(error),
+            429 => ExaError::RateLimited {
+                retry_after: error.retry_after,
+                error,
+            },
+            400 | 422 => ExaError::InvalidRequest(error),
+            500..=599 => ExaError::ServerError(error),
+            _ => ExaError::HttpError(error),
+        }
+    }
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, thiserror::Error)]
 pub struct HttpError {
     pub status: u16,
-    pub payload: HttpErrorPayload,
+    /// Structured error payload, when the body could be parsed as one
+    pub payload: Option<HttpErrorPayload>,
+    /// Raw response body, kept even when it isn't JSON (e.g. a gateway HTML page)
+    pub body: String,
+    /// Value of the `x-request-id` header, useful when contacting support
+    pub request_id: Option<String>,
+    /// Delay requested by the server through the `Retry-After` header
+    pub retry_after: Option<Duration>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct HttpErrorPayload {
+    #[serde(alias = "tag")]
     pub code: String,
+    #[serde(alias = "error")]
     pub message: String,
 }
 
+impl HttpError {
+    /// Error code reported by the API, if any
+    pub fn code(&self) -> Option<&str> {
+        self.payload.as_ref().map(|payload| payload.code.as_str())
+    }
+
+    /// Error message reported by the API, if any
+    pub fn message(&self) -> Option<&str> {
+        self.payload
+            .as_ref()
+            .map(|payload| payload.message.as_str())
+    }
+}
+
 impl fmt::Display for HttpError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{} - {} - {}",
-            self.status, self.payload.code, self.payload.message
-        )
+        match &self.payload {
+            Some(payload) => write!(
+                f,
+                "{} - {} - {}",
+                self.status, payload.code, payload.message
+            ),
+            None if self.body.trim().is_empty() => write!(f, "{}", self.status),
+            None => write!(f, "{} - {}", self.status, self.body.trim()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_error(status: u16) -> HttpError {
+        HttpError {
+            status,
+            payload: None,
+            body: String::new(),
+            request_id: None,
+            retry_after: Some(Duration::from_secs(3)),
+        }
+    }
+
+    #[test]
+    fn test_http_errors_are_classified_by_status() {
+        assert!(matches!(
+            ExaError::from(http_error(401)),
+            ExaError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            ExaError::from(http_error(402)),
+            ExaError::InsufficientCredits(_)
+        ));
+        assert!(matches!(
+            ExaError::from(http_error(422)),
+            ExaError::InvalidRequest(_)
+        ));
+        assert!(matches!(
+            ExaError::from(http_error(503)),
+            ExaError::ServerError(_)
+        ));
+        assert!(matches!(
+            ExaError::from(http_error(404)),
+            ExaError::HttpError(_)
+        ));
+        match ExaError::from(http_error(429)) {
+            ExaError::RateLimited { retry_after, .. } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(3)))
+            }
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(ExaError::from(http_error(429)).is_retryable());
+        assert!(ExaError::from(http_error(503)).is_retryable());
+        assert!(!ExaError::from(http_error(501)).is_retryable());
+        assert!(!ExaError::from(http_error(400)).is_retryable());
+        assert!(!ExaError::from(http_error(401)).is_retryable());
+
+        let exhausted = ExaError::RetriesExhausted {
+            attempts: 3,
+            last: Box::new(ExaError::from(http_error(502))),
+        };
+        assert!(exhausted.is_retryable());
+        assert_eq!(exhausted.status(), Some(502));
     }
 }
@@ -2,38 +2,54 @@ use anyhow::Result;
 use url::Url;
 
 use crate::{
-    search::{SearchContent, SearchResult},
+    cost::CostDollars,
+    search::{ResolvedSearchType, SearchContent, SearchResult},
     Exa, ExaError,
 };
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct FindSimilarRequest {
     pub url: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "numResults")]
     pub num_results: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "includeDomains")]
     pub include_domains: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "excludeDomains")]
     pub exclude_domains: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "startCrawlDate")]
     pub start_crawl_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "endCrawlDate")]
     pub end_crawl_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "startPublishedDate")]
     pub start_published_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "endPublishedDate")]
     pub end_published_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "includeText")]
     pub include_text: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "excludeText")]
     pub exclude_text: Option<Vec<String>>,
+    /// Whether to exclude links from the same domain as the input URL
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "excludeSourceDomain"
+    )]
+    pub exclude_source_domain: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contents: Option<SearchContent>,
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct FindSimilarResponse {
     pub results: Vec<SearchResult>,
+    /// Identifier of the request, useful when contacting support
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// The search type used to find similar links
+    #[serde(rename = "resolvedSearchType")]
+    pub resolved_search_type: Option<ResolvedSearchType>,
+    /// Cost of the request
+    #[serde(rename = "costDollars")]
+    pub cost_dollars: Option<CostDollars>,
 }
 
 impl FindSimilarRequest {
@@ -62,6 +78,70 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::{Livecrawl, SearchContentTextType};
+
+    #[test]
+    fn test_find_similar_request_serialization() -> Result<()> {
+        let request = FindSimilarRequest {
+            url: "https://example.com".to_string(),
+            num_results: Some(5),
+            include_domains: Some(vec!["example.org".to_string()]),
+            exclude_domains: Some(vec!["spam.com".to_string()]),
+            start_crawl_date: Some("2023-01-01".to_string()),
+            end_crawl_date: Some("2023-12-31".to_string()),
+            start_published_date: Some("2022-01-01".to_string()),
+            end_published_date: Some("2022-12-31".to_string()),
+            include_text: Some(vec!["rust".to_string()]),
+            exclude_text: Some(vec!["ads".to_string()]),
+            exclude_source_domain: Some(true),
+            contents: Some(SearchContent {
+                text: Some(SearchContentTextType::Bool(true)),
+                ..Default::default()
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "url": "https://example.com",
+                "numResults": 5,
+                "includeDomains": ["example.org"],
+                "excludeDomains": ["spam.com"],
+                "startCrawlDate": "2023-01-01",
+                "endCrawlDate": "2023-12-31",
+                "startPublishedDate": "2022-01-01",
+                "endPublishedDate": "2022-12-31",
+                "includeText": ["rust"],
+                "excludeText": ["ads"],
+                "excludeSourceDomain": true,
+                "contents": {"text": true}
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_similar_livecrawl_serialization() -> Result<()> {
+        let request = FindSimilarRequest {
+            contents: Some(SearchContent {
+                livecrawl: Some(Livecrawl::Preferred),
+                livecrawl_timeout: Some(3000),
+                ..Default::default()
+            }),
+            ..FindSimilarRequest::new("https://example.com".to_string())?
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "url": "https://example.com",
+                "contents": {"livecrawl": "preferred", "livecrawlTimeout": 3000}
+            })
+        );
+
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_find_similar() -> Result<()> {
@@ -74,6 +154,8 @@ mod tests {
             .with_header("content-type", "application/json")
             .with_body(
                 json!({
+                    "requestId": "req_123",
+                    "costDollars": {"total": 0.005},
                     "results": [{
                         "id": "test_id",
                         "title": "Test Title",
@@ -110,6 +192,8 @@ mod tests {
             Some("2023-01-01".to_string())
         );
         assert_eq!(response.results[0].author, Some("Test Author".to_string()));
+        assert_eq!(response.request_id, Some("req_123".to_string()));
+        assert_eq!(response.cost_dollars.map(|cost| cost.total), Some(0.005));
 
         Ok(())
     }
@@ -145,12 +229,12 @@ mod tests {
         let result = exa.find_similar(request).await;
 
         assert!(result.is_err());
-        if let Err(ExaError::HttpError(error)) = result {
+        if let Err(ExaError::InvalidRequest(error)) = result {
             assert_eq!(error.status, 400);
-            assert_eq!(error.payload.code, "bad_request");
-            assert_eq!(error.payload.message, "Invalid request parameters");
+            assert_eq!(error.code(), Some("bad_request"));
+            assert_eq!(error.message(), Some("Invalid request parameters"));
         } else {
-            panic!("Expected HttpError");
+            panic!("Expected InvalidRequest");
         }
 
         Ok(())
@@ -1,45 +1,172 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
+use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
-use crate::{Exa, ExaError};
+use crate::{
+    cost::CostDollars,
+    search::{Livecrawl, SearchExtras, SearchResult, SearchResultExtras, SubpageTarget, Summary},
+    Exa, ExaError,
+};
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct ContentsRequest {
+    /// Ids of results returned by a previous search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub ids: Vec<String>,
+    /// Arbitrary URLs to fetch contents for
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<ContentsTextRequest>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub highlights: Option<ContentsHighlightsRequest>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<ContentsSummaryRequest>,
+    /// Whether to crawl pages live instead of using cached content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub livecrawl: Option<Livecrawl>,
+    /// Timeout for live crawling, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none", rename = "livecrawlTimeout")]
+    pub livecrawl_timeout: Option<u32>,
+    /// Number of subpages to crawl for each page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subpages: Option<u32>,
+    /// Keywords used to pick which subpages to crawl, e.g. "about" or "pricing"
+    #[serde(skip_serializing_if = "Option::is_none", rename = "subpageTarget")]
+    pub subpage_target: Option<SubpageTarget>,
+    /// Additional data to extract from each page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<SearchExtras>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ContentsTextRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxCharacters")]
     pub max_characters: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "includeHtmlTags")]
     pub include_html_tags: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ContentsHighlightsRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "numSentences")]
     pub num_sentences: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "highlightsPerUrl")]
     pub highlights_per_url: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ContentsSummaryRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<String>,
+    /// JSON schema the summary should conform to, to get structured summaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ContentsResponse {
     pub results: Vec<ContentsResult>,
+    /// Outcome of the fetch for each requested id or URL
+    #[serde(default)]
+    pub statuses: Vec<ContentStatus>,
+    /// Identifier of the request, useful when contacting support
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// Cost of the request
+    #[serde(rename = "costDollars")]
+    pub cost_dollars: Option<CostDollars>,
+}
+
+/// The outcome of `Exa::get_contents_batched`
+#[derive(Debug)]
+pub struct BatchedContentsResponse {
+    /// The merged responses of the chunks that succeeded
+    pub response: ContentsResponse,
+    /// The chunks that failed, in input order
+    pub failures: Vec<ChunkFailure>,
+}
+
+/// A chunk of `Exa::get_contents_batched` that failed as a whole
+#[derive(Debug)]
+pub struct ChunkFailure {
+    /// The request sent for the chunk, holding its ids or URLs, ready to be sent again
+    pub request: ContentsRequest,
+    pub error: ExaError,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContentStatus {
+    /// The requested id or URL
+    pub id: String,
+    pub status: ContentStatusKind,
+    /// Why the fetch failed, when `status` is `ContentStatusKind::Error`
+    pub error: Option<ContentErrorTag>,
+}
+
+impl ContentStatus {
+    pub fn is_success(&self) -> bool {
+        self.status == ContentStatusKind::Success
+    }
+
+    /// Whether the fetch failed with a transient error, so requesting it again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        !self.is_success()
+            && self.error.as_ref().is_none_or(|error| {
+                matches!(
+                    error.tag,
+                    ContentErrorKind::CrawlTimeout
+                        | ContentErrorKind::CrawlLivecrawlTimeout
+                        | ContentErrorKind::CrawlUnknownError
+                        | ContentErrorKind::Unknown
+                )
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentStatusKind {
+    Success,
+    Error,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContentErrorTag {
+    pub tag: ContentErrorKind,
+    /// HTTP status returned by the crawled page, if any
+    #[serde(rename = "httpStatusCode")]
+    pub http_status_code: Option<u16>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContentErrorKind {
+    CrawlNotFound,
+    CrawlTimeout,
+    CrawlLivecrawlTimeout,
+    SourceNotAvailable,
+    CrawlUnknownError,
+    #[serde(other)]
+    Unknown,
+}
+
+impl ContentsResponse {
+    /// Splits the response into the fetched results and the statuses of the ids or URLs that
+    /// failed, so that only the latter need to be requested again.
+    pub fn split_failures(self) -> (Vec<ContentsResult>, Vec<ContentStatus>) {
+        let failures = self
+            .statuses
+            .into_iter()
+            .filter(|status| !status.is_success())
+            .collect();
+        (self.results, failures)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,9 +174,47 @@ pub struct ContentsResult {
     pub id: String,
     pub url: String,
     pub title: String,
+    #[serde(rename = "publishedDate")]
+    pub published_date: Option<String>,
+    pub author: Option<String>,
     pub text: Option<String>,
     pub highlights: Option<Vec<String>>,
+    #[serde(rename = "highlightScores")]
     pub highlight_scores: Option<Vec<f64>>,
+    /// Summary of the page, when requested through `ContentsRequest::summary`
+    pub summary: Option<Summary>,
+    /// URL of the main image of the page, if any
+    pub image: Option<String>,
+    /// URL of the favicon of the page's domain, if any
+    pub favicon: Option<String>,
+    /// Links found on the page, when requested through `ContentsRequest::extras`
+    pub extras: Option<SearchResultExtras>,
+    /// Subpages of the page crawled alongside it, when requested
+    #[serde(default)]
+    pub subpages: Vec<SearchResult>,
+}
+
+impl ContentsRequest {
+    pub fn new(ids: Vec<String>) -> Self {
+        Self {
+            ids,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a request fetching the contents of `urls`, which must all be valid URLs.
+    pub fn from_urls(urls: Vec<String>) -> Result<Self> {
+        Self::default().with_urls(urls)
+    }
+
+    /// Adds `urls` to the request, alongside any id already present.
+    pub fn with_urls(mut self, urls: Vec<String>) -> Result<Self> {
+        for url in &urls {
+            Url::parse(url)?;
+        }
+        self.urls.extend(urls);
+        Ok(self)
+    }
 }
 
 impl Exa {
@@ -59,16 +224,154 @@ impl Exa {
     ) -> Result<ContentsResponse, ExaError> {
         self.post("/contents", request).await
     }
+
+    /// Fetches contents for any number of ids or URLs, splitting them into requests of at most
+    /// `chunk_size` items, of which at most `concurrency` run at a time.
+    ///
+    /// Every chunk shares the options of `request`. Results and statuses are merged back in the
+    /// order of `request.ids` followed by `request.urls`, and the costs are added up. The merged
+    /// response has no `request_id`, since it spans several requests.
+    ///
+    /// A chunk failing doesn't affect the others: the results of the chunks that succeeded are
+    /// returned along with the request and error of each chunk that failed, so that only those
+    /// need to be sent again.
+    pub async fn get_contents_batched(
+        &self,
+        request: ContentsRequest,
+        chunk_size: usize,
+        concurrency: usize,
+    ) -> BatchedContentsResponse {
+        let chunk_size = chunk_size.max(1);
+        let template = ContentsRequest {
+            ids: Vec::new(),
+            urls: Vec::new(),
+            ..request.clone()
+        };
+        let chunks = request
+            .ids
+            .chunks(chunk_size)
+            .map(|ids| ContentsRequest {
+                ids: ids.to_vec(),
+                ..template.clone()
+            })
+            .chain(request.urls.chunks(chunk_size).map(|urls| ContentsRequest {
+                urls: urls.to_vec(),
+                ..template.clone()
+            }))
+            .collect::<Vec<_>>();
+
+        let responses = stream::iter(chunks)
+            .map(|chunk| async move {
+                let positions = chunk
+                    .ids
+                    .iter()
+                    .chain(&chunk.urls)
+                    .enumerate()
+                    .map(|(position, id)| (id.clone(), position))
+                    .collect::<HashMap<_, _>>();
+                let mut response = match self.get_contents(chunk.clone()).await {
+                    Ok(response) => response,
+                    Err(error) => {
+                        return Err(ChunkFailure {
+                            request: chunk,
+                            error,
+                        })
+                    }
+                };
+                let position = |id: &str, url: Option<&str>| {
+                    positions
+                        .get(id)
+                        .or_else(|| url.and_then(|url| positions.get(url)))
+                        .copied()
+                        .unwrap_or(usize::MAX)
+                };
+                response
+                    .results
+                    .sort_by_key(|result| position(&result.id, Some(&result.url)));
+                response
+                    .statuses
+                    .sort_by_key(|status| position(&status.id, None));
+                Ok(response)
+            })
+            .buffered(concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        let (responses, failures): (Vec<_>, Vec<_>) =
+            responses.into_iter().partition(Result::is_ok);
+        BatchedContentsResponse {
+            response: merge_contents_responses(responses.into_iter().flatten().collect()),
+            failures: failures.into_iter().filter_map(Result::err).collect(),
+        }
+    }
+}
+
+fn merge_contents_responses(responses: Vec<ContentsResponse>) -> ContentsResponse {
+    let mut merged = ContentsResponse {
+        results: Vec::new(),
+        statuses: Vec::new(),
+        request_id: None,
+        cost_dollars: None,
+    };
+
+    for response in responses {
+        merged.results.extend(response.results);
+        merged.statuses.extend(response.statuses);
+        if let Some(cost) = response.cost_dollars {
+            let total = merged.cost_dollars.get_or_insert_with(|| CostDollars {
+                per_request_prices: cost.per_request_prices.clone(),
+                per_page_prices: cost.per_page_prices.clone(),
+                ..Default::default()
+            });
+            total.total += cost.total;
+            total.break_down.extend(cost.break_down);
+        }
+    }
+
+    merged
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use mockito::Server as MockServer;
+    use mockito::{Matcher, Server as MockServer};
     use serde_json::json;
 
     use super::*;
 
+    #[test]
+    fn test_contents_request_serialization() -> Result<()> {
+        let request = ContentsRequest {
+            ids: vec!["test_id".to_string()],
+            text: Some(ContentsTextRequest {
+                max_characters: Some(100),
+                include_html_tags: Some(false),
+            }),
+            highlights: Some(ContentsHighlightsRequest {
+                num_sentences: Some(1),
+                highlights_per_url: Some(2),
+                query: Some("test".to_string()),
+            }),
+            summary: Some(ContentsSummaryRequest {
+                query: Some("summarize".to_string()),
+                schema: Some(json!({"type": "object"})),
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "ids": ["test_id"],
+                "text": {"maxCharacters": 100, "includeHtmlTags": false},
+                "highlights": {"numSentences": 1, "highlightsPerUrl": 2, "query": "test"},
+                "summary": {"query": "summarize", "schema": {"type": "object"}}
+            })
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_contents() -> Result<()> {
         let mut server = MockServer::new_async().await;
@@ -80,13 +383,19 @@ mod tests {
             .with_header("content-type", "application/json")
             .with_body(
                 json!({
+                    "requestId": "req_123",
+                    "costDollars": {
+                        "total": 0.001,
+                        "breakDown": [{"contents": 0.001}]
+                    },
                     "results": [{
                         "id": "test_id",
                         "url": "https://example.com",
                         "title": "Test Title",
                         "text": "Test content",
+                        "summary": "Test summary",
                         "highlights": ["Test highlight"],
-                        "highlight_scores": [0.95]
+                        "highlightScores": [0.95]
                     }]
                 })
                 .to_string(),
@@ -109,7 +418,7 @@ mod tests {
                 highlights_per_url: Some(1),
                 query: Some("test".to_string()),
             }),
-            summary: None,
+            ..Default::default()
         };
 
         let response = exa.get_contents(request).await?;
@@ -119,11 +428,17 @@ mod tests {
         assert_eq!(response.results[0].url, "https://example.com");
         assert_eq!(response.results[0].title, "Test Title");
         assert_eq!(response.results[0].text, Some("Test content".to_string()));
+        assert_eq!(
+            response.results[0].summary,
+            Some(Summary::Text("Test summary".to_string()))
+        );
         assert_eq!(
             response.results[0].highlights,
             Some(vec!["Test highlight".to_string()])
         );
         assert_eq!(response.results[0].highlight_scores, Some(vec![0.95]));
+        assert_eq!(response.request_id, Some("req_123".to_string()));
+        assert_eq!(response.cost_dollars.unwrap().contents(), 0.001);
 
         Ok(())
     }
@@ -151,24 +466,336 @@ mod tests {
             .base_url(mock_url)
             .build()?;
 
-        let request = ContentsRequest {
-            ids: vec![],
-            text: None,
-            highlights: None,
-            summary: None,
-        };
+        let request = ContentsRequest::default();
 
         let result = exa.get_contents(request).await;
 
         assert!(result.is_err());
-        if let Err(ExaError::HttpError(error)) = result {
+        if let Err(ExaError::InvalidRequest(error)) = result {
             assert_eq!(error.status, 400);
-            assert_eq!(error.payload.code, "bad_request");
-            assert_eq!(error.payload.message, "Invalid request parameters");
+            assert_eq!(error.code(), Some("bad_request"));
+            assert_eq!(error.message(), Some("Invalid request parameters"));
         } else {
-            panic!("Expected HttpError");
+            panic!("Expected InvalidRequest");
         }
 
         Ok(())
     }
+
+    #[test]
+    fn test_contents_request_crawl_options_serialization() -> Result<()> {
+        let request = ContentsRequest {
+            livecrawl: Some(Livecrawl::Fallback),
+            livecrawl_timeout: Some(10000),
+            ..ContentsRequest::new(vec!["test_id".to_string()])
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "ids": ["test_id"],
+                "livecrawl": "fallback",
+                "livecrawlTimeout": 10000
+            })
+        );
+
+        let request = ContentsRequest {
+            subpages: Some(3),
+            subpage_target: Some(SubpageTarget::One("pricing".to_string())),
+            extras: Some(SearchExtras {
+                links: Some(5),
+                image_links: None,
+            }),
+            ..ContentsRequest::new(vec!["test_id".to_string()])
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "ids": ["test_id"],
+                "subpages": 3,
+                "subpageTarget": "pricing",
+                "extras": {"links": 5}
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contents_request_with_urls() -> Result<()> {
+        let request = ContentsRequest::new(vec!["test_id".to_string()])
+            .with_urls(vec!["https://example.com/page".to_string()])?;
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "ids": ["test_id"],
+                "urls": ["https://example.com/page"]
+            })
+        );
+
+        assert!(ContentsRequest::from_urls(vec![
+            "https://example.com".to_string(),
+            "not a valid url".to_string()
+        ])
+        .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_contents_by_url_with_statuses() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({
+                "urls": ["https://example.com", "https://example.org"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "results": [{
+                        "id": "https://example.com",
+                        "url": "https://example.com",
+                        "title": "Test Title",
+                        "text": "Test content"
+                    }],
+                    "statuses": [
+                        {"id": "https://example.com", "status": "success"},
+                        {
+                            "id": "https://example.org",
+                            "status": "error",
+                            "error": {"tag": "CRAWL_NOT_FOUND", "httpStatusCode": 404}
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .build()?;
+
+        let request = ContentsRequest::from_urls(vec![
+            "https://example.com".to_string(),
+            "https://example.org".to_string(),
+        ])?;
+
+        let response = exa.get_contents(request).await?;
+
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.statuses.len(), 2);
+        assert_eq!(response.statuses[1].id, "https://example.org");
+        assert_eq!(response.statuses[1].status, ContentStatusKind::Error);
+        assert_eq!(
+            response.statuses[1].error.as_ref().unwrap().tag,
+            ContentErrorKind::CrawlNotFound
+        );
+        assert_eq!(
+            response.statuses[1]
+                .error
+                .as_ref()
+                .unwrap()
+                .http_status_code,
+            Some(404)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_failures() -> Result<()> {
+        let response = serde_json::from_value::<ContentsResponse>(json!({
+            "results": [{
+                "id": "a",
+                "url": "https://a.com",
+                "title": "A",
+                "subpages": [{"id": "a/1", "url": "https://a.com/1", "title": "A1"}]
+            }],
+            "statuses": [
+                {"id": "a", "status": "success"},
+                {"id": "b", "status": "error", "error": {"tag": "CRAWL_TIMEOUT"}},
+                {"id": "c", "status": "error", "error": {"tag": "SOURCE_NOT_AVAILABLE", "httpStatusCode": 403}},
+                {"id": "d", "status": "error", "error": {"tag": "SOMETHING_NEW"}}
+            ]
+        }))?;
+
+        let (results, failures) = response.split_failures();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subpages[0].url, "https://a.com/1");
+        assert_eq!(
+            failures
+                .iter()
+                .map(|status| status.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c", "d"]
+        );
+        assert_eq!(
+            failures
+                .iter()
+                .filter(|status| status.is_retryable())
+                .map(|status| status.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "d"]
+        );
+        assert_eq!(
+            failures[2].error.as_ref().unwrap().tag,
+            ContentErrorKind::Unknown
+        );
+
+        Ok(())
+    }
+
+    fn contents_result(id: &str) -> serde_json::Value {
+        json!({"id": id, "url": format!("https://example.com/{}", id), "title": id})
+    }
+
+    #[tokio::test]
+    async fn test_get_contents_batched() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _first = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({
+                "ids": ["a", "b"],
+                "text": {"maxCharacters": 100}
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "requestId": "req_1",
+                    "results": [contents_result("b"), contents_result("a")],
+                    "statuses": [{"id": "a", "status": "success"}, {"id": "b", "status": "success"}],
+                    "costDollars": {"total": 0.002, "breakDown": [{"contents": 0.002}]}
+                })
+                .to_string(),
+            )
+            .create();
+        let _second = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({
+                "ids": ["c"],
+                "text": {"maxCharacters": 100}
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "requestId": "req_2",
+                    "results": [],
+                    "statuses": [{"id": "c", "status": "error", "error": {"tag": "CRAWL_NOT_FOUND"}}],
+                    "costDollars": {"total": 0.001, "breakDown": [{"contents": 0.001}]}
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .build()?;
+
+        let request = ContentsRequest {
+            text: Some(ContentsTextRequest {
+                max_characters: Some(100),
+                include_html_tags: None,
+            }),
+            ..ContentsRequest::new(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        };
+
+        let BatchedContentsResponse { response, failures } =
+            exa.get_contents_batched(request, 2, 2).await;
+
+        assert!(failures.is_empty());
+        assert_eq!(
+            response
+                .results
+                .iter()
+                .map(|result| result.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            response
+                .statuses
+                .iter()
+                .map(|status| status.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(response.request_id, None);
+        let cost = response.cost_dollars.unwrap();
+        assert!((cost.total - 0.003).abs() < 1e-9);
+        assert!((cost.contents() - 0.003).abs() < 1e-9);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_contents_batched_keeps_results_of_successful_chunks() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _first = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({"ids": ["a"]})))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "results": [contents_result("a")],
+                    "statuses": [{"id": "a", "status": "success"}],
+                    "costDollars": {"total": 0.001}
+                })
+                .to_string(),
+            )
+            .create();
+        let _second = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({"ids": ["b"]})))
+            .with_status(400)
+            .with_body(json!({"code": "invalid_id", "message": "Invalid id"}).to_string())
+            .create();
+        let _third = server
+            .mock("POST", "/contents")
+            .match_body(Matcher::Json(json!({"ids": ["c"]})))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "results": [contents_result("c")],
+                    "statuses": [{"id": "c", "status": "success"}],
+                    "costDollars": {"total": 0.001}
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .build()?;
+
+        let request = ContentsRequest::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let BatchedContentsResponse { response, failures } =
+            exa.get_contents_batched(request, 1, 3).await;
+
+        assert_eq!(
+            response
+                .results
+                .iter()
+                .map(|result| result.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "c"]
+        );
+        assert!((response.cost_dollars.unwrap().total - 0.002).abs() < 1e-9);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].request.ids, vec!["b".to_string()]);
+        assert!(matches!(failures[0].error, ExaError::InvalidRequest(_)));
+
+        Ok(())
+    }
 }
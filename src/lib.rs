@@ -1,30 +1,113 @@
+mod answer;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cost;
 mod error;
 mod find_similar;
 mod get_contents;
+pub mod middleware;
+mod pagination;
+mod rate_limit;
+mod research;
+mod retry;
 mod search;
+#[cfg(feature = "tower")]
+mod service;
+mod sse;
+pub mod transport;
+pub mod webhooks;
+mod websets;
 
-use anyhow::{anyhow, Result};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Response,
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT},
+    Method, StatusCode,
 };
 use secrecy::{ExposeSecret, SecretString};
 use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::{
+    cost::ReportedCost,
+    middleware::{Middleware, Next},
+    rate_limit::RateLimiter,
+    transport::{
+        ClientOptions, HttpRequest, HttpResponse, HttpTransport, ReqwestTransport, TransportError,
+        TransportErrorKind,
+    },
+};
+
+pub use crate::{
+    answer::*,
+    cost::*,
+    error::*,
+    find_similar::*,
+    get_contents::*,
+    pagination::{ListParams, Page},
+    rate_limit::RateLimit,
+    research::*,
+    retry::RetryPolicy,
+    search::*,
+    transport::HttpVersionPreference,
+    websets::*,
+};
 
-pub use crate::{error::*, find_similar::*, get_contents::*, search::*};
+#[cfg(feature = "tower")]
+pub use crate::service::{ExaRequest, ExaResponse};
 
 pub const BASE_URL: &str = "https://api.exa.ai";
 pub const API_KEY_HEADER: &str = "x-api-key";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
+const DEFAULT_USER_AGENT: &str = concat!("exa-sdk/", env!("CARGO_PKG_VERSION"));
+
+/// Characters escaped in a path segment: all but the unreserved characters of RFC 3986
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Client for the Exa API.
+///
+/// Cloning is cheap: clones share the same connection pool, rate limiter and cost tracker, so a
+/// single client can be handed to many concurrent tasks.
+#[derive(Clone)]
 pub struct Exa {
-    client: reqwest::Client,
+    inner: Arc<ExaInner>,
+}
+
+/// State shared by the clones of a client, generic over the HTTP client so that the blocking
+/// client can reuse it.
+struct ExaInner<C = Arc<dyn HttpTransport>> {
+    client: C,
     api_key: SecretString,
     base_url: String,
+    user_agent: HeaderValue,
+    retry_policy: RetryPolicy,
+    middleware: Vec<Arc<dyn Middleware>>,
+    rate_limiter: RateLimiter,
+    cost_tracker: Option<Arc<CostTracker>>,
+    cost_tag: Option<String>,
 }
 
 pub struct ExaBuilder {
     api_key: Option<SecretString>,
     base_url: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    http_client: Option<reqwest::Client>,
+    client_options: ClientOptions,
+    user_agent_suffix: Option<String>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    rate_limit: Option<RateLimit>,
+    endpoint_rate_limits: HashMap<String, RateLimit>,
+    cost_tracker: Option<Arc<CostTracker>>,
+    cost_tag: Option<String>,
 }
 
 impl Exa {
@@ -32,56 +115,259 @@ impl Exa {
         ExaBuilder {
             api_key: None,
             base_url: None,
+            retry_policy: None,
+            transport: None,
+            http_client: None,
+            client_options: ClientOptions::default(),
+            user_agent_suffix: None,
+            middleware: Vec::new(),
+            rate_limit: None,
+            endpoint_rate_limits: HashMap::new(),
+            cost_tracker: None,
+            cost_tag: None,
         }
     }
 
+    /// The cost tracker attached to this client, if any.
+    pub fn cost_tracker(&self) -> Option<&Arc<CostTracker>> {
+        self.inner.cost_tracker.as_ref()
+    }
+
     pub(crate) async fn post<P, S, D>(&self, path: P, request: S) -> Result<D, ExaError>
     where
         P: Into<String>,
         S: Serialize,
         D: DeserializeOwned,
     {
-        let headers = self.build_headers();
+        let path = path.into();
+        let response = self
+            .send(Method::POST, &path, Some(json_body(&request)?), true)
+            .await?;
+        self.handle_response(&path, response).await
+    }
 
+    /// Sends a POST request creating a paid job, which is only retried if the API can't have
+    /// processed it, so that a failure reported after the job was created doesn't create a
+    /// second one. See `RetryPolicy`.
+    pub(crate) async fn post_non_idempotent<P, S, D>(
+        &self,
+        path: P,
+        request: S,
+    ) -> Result<D, ExaError>
+    where
+        P: Into<String>,
+        S: Serialize,
+        D: DeserializeOwned,
+    {
+        let path = path.into();
         let response = self
-            .client
-            .post(format!("{}{}", self.base_url, path.into()))
-            .headers(headers)
-            .json(&request)
-            .send()
+            .send(Method::POST, &path, Some(json_body(&request)?), false)
             .await?;
+        self.handle_response(&path, response).await
+    }
 
-        handle_response(response).await
+    pub(crate) async fn get<P, Q, D>(&self, path: P, query: &Q) -> Result<D, ExaError>
+    where
+        P: Into<String>,
+        Q: Serialize + ?Sized,
+        D: DeserializeOwned,
+    {
+        let path = path.into();
+        let query = serde_urlencoded::to_string(query)
+            .map_err(|error| TransportError::new(TransportErrorKind::Builder, error))?;
+        let url = if query.is_empty() {
+            path.clone()
+        } else {
+            format!("{}?{}", path, query)
+        };
+        let response = self.send(Method::GET, &url, None, true).await?;
+        self.handle_response(&path, response).await
     }
 
-    fn build_headers(&self) -> HeaderMap {
+    pub(crate) async fn patch<P, S, D>(&self, path: P, request: S) -> Result<D, ExaError>
+    where
+        P: Into<String>,
+        S: Serialize,
+        D: DeserializeOwned,
+    {
+        let path = path.into();
+        let response = self
+            .send(Method::PATCH, &path, Some(json_body(&request)?), true)
+            .await?;
+        self.handle_response(&path, response).await
+    }
+
+    pub(crate) async fn delete<P, D>(&self, path: P) -> Result<D, ExaError>
+    where
+        P: Into<String>,
+        D: DeserializeOwned,
+    {
+        let path = path.into();
+        let response = self.send(Method::DELETE, &path, None, true).await?;
+        self.handle_response(&path, response).await
+    }
+
+    /// Sends a request through the transport, retrying transient failures, and returns the
+    /// successful response without consuming its body.
+    ///
+    /// `path` may include a query string; `body` is sent as JSON. Requests that aren't
+    /// `idempotent` are only retried when they can't have been processed. Fails with
+    /// `ExaError::BudgetExceeded` without sending anything if the attached cost tracker has
+    /// spent its budget.
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Bytes>,
+        idempotent: bool,
+    ) -> Result<HttpResponse, ExaError> {
+        self.inner.check_budget()?;
+
+        let mut headers = self.inner.headers();
+        if body.is_some() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+        let request = HttpRequest {
+            method,
+            url: self.inner.url(path),
+            headers,
+            body,
+        };
+        let retry_policy = &self.inner.retry_policy;
+        let mut attempt = 1;
+
+        loop {
+            self.inner.rate_limiter.acquire(path).await;
+
+            let result = Next::new(&self.inner.middleware, &*self.inner.client)
+                .run(request.clone())
+                .await;
+
+            let delay = match &result {
+                Ok(response) => retry_policy.retry_delay(
+                    attempt,
+                    idempotent,
+                    Ok((response.status, &response.headers)),
+                ),
+                Err(ExaError::ClientError(error) | ExaError::Timeout(error)) => {
+                    retry_policy.retry_delay(attempt, idempotent, Err(error))
+                }
+                Err(_) => None,
+            };
+
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            let outcome = match result {
+                Ok(response) => check_response(response).await,
+                Err(error) => Err(error),
+            };
+
+            return outcome.map_err(|last| retry::exhausted(attempt, last));
+        }
+    }
+
+    /// Decodes a successful response, recording the cost it reports against the cost tracker.
+    async fn handle_response<D>(&self, path: &str, response: HttpResponse) -> Result<D, ExaError>
+    where
+        D: DeserializeOwned,
+    {
+        let body = response.text().await?;
+        self.inner.decode(path, body)
+    }
+}
+
+impl<C> ExaInner<C> {
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
         headers.insert(
             API_KEY_HEADER,
-            HeaderValue::from_str(&self.api_key.expose_secret())
+            HeaderValue::from_str(self.api_key.expose_secret())
                 .expect("couldn't create header value"),
         );
+        headers.insert(USER_AGENT, self.user_agent.clone());
         headers
     }
+
+    /// Fails with `ExaError::BudgetExceeded` if the attached cost tracker has spent its budget.
+    fn check_budget(&self) -> Result<(), ExaError> {
+        match self
+            .cost_tracker
+            .as_ref()
+            .and_then(|tracker| tracker.overspent())
+        {
+            Some((spent, budget)) => Err(ExaError::BudgetExceeded { spent, budget }),
+            None => Ok(()),
+        }
+    }
+
+    /// Decodes the body of a successful response, recording the cost it reports against the
+    /// cost tracker.
+    fn decode<D>(&self, path: &str, body: String) -> Result<D, ExaError>
+    where
+        D: DeserializeOwned,
+    {
+        if let Some(tracker) = &self.cost_tracker {
+            if let Ok(ReportedCost {
+                cost_dollars: Some(cost),
+            }) = serde_json::from_str::<ReportedCost>(&body)
+            {
+                tracker.record(path, self.cost_tag.as_deref(), &cost);
+            }
+        }
+        serde_json::from_str::<D>(&body).map_err(|source| ExaError::Decode { source, body })
+    }
 }
 
-async fn handle_response<D>(response: Response) -> Result<D, ExaError>
+async fn check_response(response: HttpResponse) -> Result<HttpResponse, ExaError> {
+    let status = response.status;
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let headers = response.headers.clone();
+    let body = response.text().await?;
+    Err(http_error(status, &headers, body))
+}
+
+/// Percent-encodes a caller-supplied id, so that it's inserted in a URL path as a single
+/// segment even if it contains `/`, `?` or `#`.
+pub(crate) fn path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// Serializes a request body.
+pub(crate) fn json_body<S>(request: &S) -> Result<Bytes, ExaError>
 where
-    D: DeserializeOwned,
+    S: Serialize + ?Sized,
 {
-    let status = response.status();
-    if !status.is_success() {
-        let text = response.text().await?;
-        dbg!(&text);
-        let payload = serde_json::from_str::<HttpErrorPayload>(&text).unwrap();
-        return Err(ExaError::HttpError(HttpError {
-            status: status.as_u16(),
-            payload,
-        }));
-    }
-
-    let response = response.json::<D>().await?;
-    Ok(response)
+    serde_json::to_vec(request)
+        .map(Bytes::from)
+        .map_err(|error| TransportError::new(TransportErrorKind::Builder, error).into())
+}
+
+/// Builds the error for a non-success response.
+fn http_error(status: StatusCode, headers: &HeaderMap, body: String) -> ExaError {
+    let request_id = headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+
+    HttpError {
+        status: status.as_u16(),
+        payload: serde_json::from_str::<HttpErrorPayload>(&body).ok(),
+        body,
+        request_id,
+        retry_after: retry::retry_after(headers),
+    }
+    .into()
 }
 
 impl ExaBuilder {
@@ -90,17 +376,180 @@ impl ExaBuilder {
         self
     }
 
+    /// Sets the URL of the API, e.g. to go through a proxy. Defaults to `BASE_URL`.
+    ///
+    /// `build` fails if it isn't an absolute `http` or `https` URL.
     pub fn base_url(mut self, base_url: String) -> Self {
         self.base_url = Some(base_url);
         self
     }
 
-    pub fn build(self) -> Result<Exa> {
+    /// Sets the policy used to retry transient failures. Defaults to `RetryPolicy::default()`.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Sends requests through `transport` instead of the default `ReqwestTransport`.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sends requests with `http_client`, e.g. to share a connection pool or to configure
+    /// TLS, instead of a client built from the options below.
+    ///
+    /// `build` fails if any of those options is also set, since they can't be applied to an
+    /// existing client.
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sets how long to wait for a connection to the API to be established.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.client_options.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for each read of the response, e.g. between two chunks of a
+    /// streamed answer.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.client_options.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long each attempt may take in total, from connecting to reading the whole
+    /// response. Retries get a fresh timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.client_options.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends all requests through the proxy at `proxy`, e.g. `http://proxy.internal:3128`.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.client_options.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Sets whether to accept gzip-compressed responses. Enabled by default.
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.client_options.gzip = Some(enable);
+        self
+    }
+
+    /// Sets whether to accept brotli-compressed responses. Enabled by default.
+    pub fn brotli(mut self, enable: bool) -> Self {
+        self.client_options.brotli = Some(enable);
+        self
+    }
+
+    /// Sets which HTTP version to speak with the API.
+    pub fn http_version(mut self, http_version: HttpVersionPreference) -> Self {
+        self.client_options.http_version = http_version;
+        self
+    }
+
+    /// Appends `suffix` to the `User-Agent` sent with every request, e.g. to identify the
+    /// application calling the API.
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Appends `middleware` to the chain run around every request. See `middleware`.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Limits the rate of requests sent by this client and all its clones.
+    ///
+    /// Requests over the limit wait for their turn rather than failing. Retries count against
+    /// the limit too.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Limits the rate of requests to `endpoint` (e.g. `/search`), and to any path under it.
+    ///
+    /// Endpoint limits apply on top of the limit set with `rate_limit`.
+    pub fn endpoint_rate_limit(
+        mut self,
+        endpoint: impl Into<String>,
+        rate_limit: RateLimit,
+    ) -> Self {
+        self.endpoint_rate_limits
+            .insert(endpoint.into(), rate_limit);
+        self
+    }
+
+    /// Attaches a tracker adding up the dollars spent by this client, and enforcing its budget.
+    ///
+    /// The same tracker can be attached to several clients to give them a shared budget.
+    pub fn cost_tracker(mut self, cost_tracker: Arc<CostTracker>) -> Self {
+        self.cost_tracker = Some(cost_tracker);
+        self
+    }
+
+    /// Sets the tag under which the cost tracker records this client's spending.
+    pub fn cost_tag(mut self, cost_tag: impl Into<String>) -> Self {
+        self.cost_tag = Some(cost_tag.into());
+        self
+    }
+
+    pub fn build(mut self) -> Result<Exa> {
+        if (self.transport.is_some() || self.http_client.is_some())
+            && !self.client_options.is_default()
+        {
+            bail!("HTTP client options can't be applied to a caller-supplied client or transport");
+        }
+
+        let transport: Arc<dyn HttpTransport> =
+            match (self.transport.take(), self.http_client.take()) {
+                (Some(_), Some(_)) => {
+                    bail!("Only one of a transport and an HTTP client can be set")
+                }
+                (Some(transport), None) => transport,
+                (None, Some(http_client)) => Arc::new(ReqwestTransport::new(http_client)),
+                (None, None) => Arc::new(ReqwestTransport::new(self.client_options.build()?)),
+            };
+
         Ok(Exa {
-            client: reqwest::Client::new(),
+            inner: Arc::new(self.build_inner(transport)?),
+        })
+    }
+
+    fn build_inner<C>(self, client: C) -> Result<ExaInner<C>> {
+        let base_url = self.base_url.unwrap_or_else(|| BASE_URL.to_string());
+        let url = Url::parse(&base_url)
+            .map_err(|error| anyhow!("Invalid base URL {:?}: {}", base_url, error))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!(
+                "Invalid base URL {:?}: expected an http or https URL",
+                base_url
+            );
+        }
+
+        let user_agent = match &self.user_agent_suffix {
+            Some(suffix) => format!("{} {}", DEFAULT_USER_AGENT, suffix),
+            None => DEFAULT_USER_AGENT.to_string(),
+        };
+        let user_agent = HeaderValue::from_str(&user_agent)
+            .map_err(|_| anyhow!("Invalid user agent {:?}", user_agent))?;
+
+        Ok(ExaInner {
+            client,
             api_key: self.api_key.or_else(|| std::env::var("EXA_API_KEY").ok().map(SecretString::new))
                 .ok_or_else(|| anyhow!("API key is required. Set it explicitly or use the EXA_API_KEY environment variable"))?,
-            base_url: self.base_url.unwrap_or_else(|| BASE_URL.to_string()),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            user_agent,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            middleware: self.middleware,
+            rate_limiter: RateLimiter::new(self.rate_limit, self.endpoint_rate_limits)?,
+            cost_tracker: self.cost_tracker,
+            cost_tag: self.cost_tag,
         })
     }
 }
@@ -0,0 +1,277 @@
+//! Hooks to inspect or modify the requests `Exa` sends and the responses it receives.
+//!
+//! Middleware registered with `ExaBuilder::middleware` runs in registration order around every
+//! attempt of every request: each one receives the outgoing request and a `Next` handle to pass
+//! it on to the following middleware and eventually the transport. A middleware can modify the
+//! request before passing it on, inspect or replace the response on the way back, or return a
+//! response of its own without calling `Next` at all.
+//!
+//! Middleware runs inside the retry loop, so it sees each attempt separately, and its errors are
+//! returned as is. It isn't run by the blocking client.
+//!
+//! ```rust,ignore
+//! # use exa_sdk::{middleware::{Middleware, Next}, transport::{HttpRequest, HttpResponse}, ExaError};
+//! # use futures::future::{BoxFuture, FutureExt};
+//! struct Tracing;
+//!
+//! impl Middleware for Tracing {
+//!     fn handle<'a>(
+//!         &'a self,
+//!         mut request: HttpRequest,
+//!         next: Next<'a>,
+//!     ) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+//!         request.headers.insert("traceparent", current_trace_parent());
+//!         next.run(request)
+//!     }
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
+
+use crate::{
+    transport::{HttpRequest, HttpResponse, HttpTransport},
+    ExaError,
+};
+
+pub trait Middleware: Send + Sync {
+    /// Handles a request, usually by passing it on with `next.run(request)`.
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, ExaError>>;
+}
+
+/// The rest of the middleware chain, ending with the transport
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn HttpTransport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middleware: &'a [Arc<dyn Middleware>],
+        transport: &'a dyn HttpTransport,
+    ) -> Self {
+        Self {
+            middleware,
+            transport,
+        }
+    }
+
+    /// Passes the request on to the next middleware, or sends it if there's none left.
+    pub fn run(self, request: HttpRequest) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(rest, self.transport)),
+            None => self.transport.send(request).err_into().boxed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use anyhow::Result;
+    use mockito::{Matcher, Server as MockServer};
+    use reqwest::{
+        header::{HeaderMap, HeaderValue},
+        StatusCode,
+    };
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Exa, SearchRequest};
+
+    /// Records the order it runs in and adds a header named after itself
+    struct Tag {
+        name: &'static str,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            mut request: HttpRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+            self.log.lock().unwrap().push(self.name);
+            request
+                .headers
+                .insert(self.name, HeaderValue::from_static("1"));
+            next.run(request)
+        }
+    }
+
+    /// Forces `includeDomains` on search requests
+    struct Compliance;
+
+    impl Middleware for Compliance {
+        fn handle<'a>(
+            &'a self,
+            mut request: HttpRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+            async move {
+                let to_error = |error: serde_json::Error| ExaError::Middleware(error.into());
+                if let Some(mut body) = request.json_body().map_err(to_error)? {
+                    body["includeDomains"] = json!(["example.com"]);
+                    request.set_json_body(&body).map_err(to_error)?;
+                }
+                next.run(request).await
+            }
+            .boxed()
+        }
+    }
+
+    /// Answers every request without sending it
+    struct Canned;
+
+    impl Middleware for Canned {
+        fn handle<'a>(
+            &'a self,
+            _request: HttpRequest,
+            _next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+            let body = json!({"results": [], "requestId": "canned"}).to_string();
+            futures::future::ok(HttpResponse::new(StatusCode::OK, HeaderMap::new(), body)).boxed()
+        }
+    }
+
+    /// Keeps a copy of every response body
+    struct Recorder {
+        bodies: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Recorder {
+        fn handle<'a>(
+            &'a self,
+            request: HttpRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, ExaError>> {
+            async move {
+                let response = next.run(request).await?;
+                let (status, headers) = (response.status, response.headers.clone());
+                let body = response.bytes().await?;
+                self.bodies
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&body).into_owned());
+                Ok(HttpResponse::new(status, headers, body))
+            }
+            .boxed()
+        }
+    }
+
+    fn search_request() -> SearchRequest {
+        SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_in_order_and_modifies_requests() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock("POST", "/search")
+            .match_header("first", "1")
+            .match_header("second", "1")
+            .match_body(Matcher::PartialJson(json!({
+                "query": "test query",
+                "includeDomains": ["example.com"]
+            })))
+            .with_status(200)
+            .with_body(json!({"results": []}).to_string())
+            .create();
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .middleware(Tag {
+                name: "first",
+                log: log.clone(),
+            })
+            .middleware(Compliance)
+            .middleware(Tag {
+                name: "second",
+                log: log.clone(),
+            })
+            .build()?;
+
+        exa.search(search_request()).await?;
+
+        m.assert();
+        assert_eq!(*log.lock().unwrap(), vec!["first", "second"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_middleware_can_short_circuit() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server.mock("POST", "/search").expect(0).create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .middleware(Canned)
+            .build()?;
+
+        let response = exa.search(search_request()).await?;
+
+        m.assert();
+        assert_eq!(response.request_id, Some("canned".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_middleware_can_inspect_responses() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_body(json!({"results": [], "requestId": "req_1"}).to_string())
+            .create();
+
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .middleware(Recorder {
+                bodies: bodies.clone(),
+            })
+            .build()?;
+
+        let response = exa.search(search_request()).await?;
+
+        assert_eq!(response.request_id, Some("req_1".to_string()));
+        assert!(bodies.lock().unwrap()[0].contains("req_1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_debug_redacts_api_key() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret_key"));
+        let request = HttpRequest {
+            method: reqwest::Method::POST,
+            url: "https://exa.test/search".to_string(),
+            headers,
+            body: None,
+        };
+
+        let debug = format!("{:?}", request);
+        assert!(!debug.contains("secret_key"));
+        assert!(debug.contains("[REDACTED]"));
+        assert_eq!(request.headers["x-api-key"], "secret_key");
+    }
+}
@@ -0,0 +1,64 @@
+use std::{collections::VecDeque, future::Future};
+
+use futures::{stream, Stream};
+
+use crate::ExaError;
+
+/// Query parameters for cursor-paginated list endpoints
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct ListParams {
+    /// Cursor returned as `next_cursor` by the previous page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Maximum number of items per page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// A page of results from a cursor-paginated list endpoint
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    /// Whether more items are available after this page
+    #[serde(rename = "hasMore", default)]
+    pub has_more: bool,
+    /// Cursor to pass as `ListParams::cursor` to fetch the next page
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
+}
+
+/// Turns a page fetcher into a stream of items, following `next_cursor` until the last page.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    limit: Option<u32>,
+    fetch: F,
+) -> impl Stream<Item = Result<T, ExaError>> + 'a
+where
+    T: 'a,
+    F: FnMut(ListParams) -> Fut + 'a,
+    Fut: Future<Output = Result<Page<T>, ExaError>> + 'a,
+{
+    let state = (fetch, VecDeque::new(), Some(ListParams::default()));
+
+    stream::unfold(state, move |(mut fetch, mut items, mut next)| async move {
+        loop {
+            if let Some(item) = items.pop_front() {
+                return Some((Ok(item), (fetch, items, next)));
+            }
+
+            let params = next.take()?;
+            match fetch(ListParams { limit, ..params }).await {
+                Ok(page) => {
+                    items.extend(page.data);
+                    next = match page.next_cursor {
+                        Some(cursor) if page.has_more => Some(ListParams {
+                            cursor: Some(cursor),
+                            limit,
+                        }),
+                        _ => None,
+                    };
+                }
+                Err(error) => return Some((Err(error), (fetch, items, None))),
+            }
+        }
+    })
+}
@@ -0,0 +1,293 @@
+use std::{cmp::Reverse, collections::HashMap, sync::Mutex, time::Duration};
+
+use anyhow::{bail, Result};
+use tokio::time::Instant;
+
+/// A token-bucket limit on how many requests `Exa` sends.
+///
+/// The bucket holds up to `burst` tokens and refills at `requests_per_second`. Every request
+/// takes a token; when the bucket is empty, requests wait for their turn instead of failing.
+///
+/// `requests_per_second` must be finite and positive, otherwise building the client fails.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimit {
+    /// Sustained number of requests allowed per second
+    pub requests_per_second: f64,
+    /// Number of requests that can be sent at once after a quiet period
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// A limit of `requests_per_second`, with a burst of one second worth of requests.
+    pub fn per_second(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second,
+            burst: requests_per_second.ceil().max(1.0) as u32,
+        }
+    }
+
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.requests_per_second.is_finite() || self.requests_per_second <= 0.0 {
+            bail!(
+                "Invalid rate limit of {} requests per second: expected a finite, positive rate",
+                self.requests_per_second
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Rate limits applied by a client, shared by all its clones
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    global: Option<TokenBucket>,
+    /// Buckets keyed by path prefix, e.g. `/search` or `/websets/v0/websets`
+    endpoints: Vec<(String, TokenBucket)>,
+}
+
+impl RateLimiter {
+    /// Fails if any of the limits has a rate that isn't finite and positive.
+    pub(crate) fn new(
+        global: Option<RateLimit>,
+        endpoints: HashMap<String, RateLimit>,
+    ) -> Result<Self> {
+        for limit in global.iter().chain(endpoints.values()) {
+            limit.validate()?;
+        }
+
+        let mut endpoints = endpoints
+            .into_iter()
+            .map(|(endpoint, limit)| (endpoint, TokenBucket::new(limit)))
+            .collect::<Vec<_>>();
+        // Longest prefixes first, so the most specific limit wins.
+        endpoints.sort_by_key(|(endpoint, _)| Reverse(endpoint.len()));
+
+        Ok(Self {
+            global: global.map(TokenBucket::new),
+            endpoints,
+        })
+    }
+
+    /// Waits until a request to `path` is allowed by both the global and the endpoint limit.
+    ///
+    /// If the future is dropped before the wait is over, the reserved tokens are given back, so
+    /// cancelled requests don't delay the following ones.
+    pub(crate) async fn acquire(&self, path: &str) {
+        let buckets = self.buckets(path);
+        let delay = reserve_all(&buckets);
+        if !delay.is_zero() {
+            let mut refund = Refund(buckets);
+            tokio::time::sleep(delay).await;
+            refund.0.clear();
+        }
+    }
+
+    /// Takes a token for a request to `path` and returns how long to wait before sending it.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn reserve(&self, path: &str) -> Duration {
+        reserve_all(&self.buckets(path))
+    }
+
+    /// The buckets a request to `path` takes a token from.
+    fn buckets(&self, path: &str) -> Vec<&TokenBucket> {
+        let endpoint = self
+            .endpoints
+            .iter()
+            .find(|(endpoint, _)| matches_endpoint(path, endpoint))
+            .map(|(_, bucket)| bucket);
+
+        [self.global.as_ref(), endpoint]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+fn reserve_all(buckets: &[&TokenBucket]) -> Duration {
+    buckets
+        .iter()
+        .map(|bucket| bucket.reserve())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Gives a token back to each of its buckets when dropped, unless emptied first
+struct Refund<'a>(Vec<&'a TokenBucket>);
+
+impl Drop for Refund<'_> {
+    fn drop(&mut self) {
+        for bucket in &self.0 {
+            bucket.refund();
+        }
+    }
+}
+
+fn matches_endpoint(path: &str, endpoint: &str) -> bool {
+    let path = path.split('?').next().unwrap_or(path);
+    path.strip_prefix(endpoint)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Available tokens; negative when requests are queued waiting for a refill
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            state: Mutex::new(BucketState {
+                tokens: limit.burst as f64,
+                updated_at: Instant::now(),
+            }),
+            limit,
+        }
+    }
+
+    /// Takes a token and returns how long to wait before it's actually available.
+    ///
+    /// Tokens are reserved ahead of time, so concurrent callers queue up in order rather than
+    /// all waking up at once when the bucket refills.
+    fn reserve(&self) -> Duration {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated_at).as_secs_f64();
+        state.tokens =
+            (state.tokens + elapsed * self.limit.requests_per_second).min(self.limit.burst as f64);
+        state.updated_at = now;
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            // Rates are validated when the client is built; never panic on a request regardless.
+            Duration::try_from_secs_f64(-state.tokens / self.limit.requests_per_second)
+                .unwrap_or_default()
+        }
+    }
+
+    /// Gives back a token reserved by a request that was cancelled before being sent.
+    fn refund(&self) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.tokens = (state.tokens + 1.0).min(self.limit.burst as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_defaults_burst_to_one_second() {
+        assert_eq!(RateLimit::per_second(2.5).burst, 3);
+        assert_eq!(RateLimit::per_second(0.1).burst, 1);
+        assert_eq!(RateLimit::per_second(10.0).burst(0).burst, 1);
+    }
+
+    #[test]
+    fn test_token_bucket_reserves_ahead() {
+        let bucket = TokenBucket::new(RateLimit::per_second(10.0).burst(2));
+
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+
+        let first = bucket.reserve();
+        let second = bucket.reserve();
+        assert!(first > Duration::from_millis(90) && first <= Duration::from_millis(100));
+        assert!(second > Duration::from_millis(190) && second <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_endpoint_limits_match_path_prefixes() {
+        assert!(matches_endpoint("/search", "/search"));
+        assert!(matches_endpoint(
+            "/websets/v0/websets/ws_1",
+            "/websets/v0/websets"
+        ));
+        assert!(!matches_endpoint("/searches", "/search"));
+        assert!(!matches_endpoint("/contents", "/search"));
+    }
+
+    #[test]
+    fn test_rejects_rates_that_are_not_finite_and_positive() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(RateLimiter::new(Some(RateLimit::per_second(rate)), HashMap::new()).is_err());
+            assert!(RateLimiter::new(
+                None,
+                HashMap::from([("/search".to_string(), RateLimit::per_second(rate))]),
+            )
+            .is_err());
+        }
+        assert!(RateLimiter::new(Some(RateLimit::per_second(0.5)), HashMap::new()).is_ok());
+
+        let result = crate::Exa::builder()
+            .api_key("test_key".to_string())
+            .rate_limit(RateLimit::per_second(0.0))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_acquire_gives_its_token_back() {
+        let limiter = RateLimiter::new(
+            Some(RateLimit::per_second(10.0).burst(1)),
+            HashMap::from([("/search".to_string(), RateLimit::per_second(10.0).burst(1))]),
+        )
+        .unwrap();
+
+        limiter.acquire("/search").await;
+        for _ in 0..100 {
+            assert!(limiter.acquire("/search").now_or_never().is_none());
+        }
+
+        // Without refunds this would wait for the 100 cancelled requests, i.e. 10 seconds.
+        let start = Instant::now();
+        limiter.acquire("/search").await;
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_the_most_specific_limit() {
+        let limiter = RateLimiter::new(
+            Some(RateLimit::per_second(1000.0)),
+            HashMap::from([
+                ("/search".to_string(), RateLimit::per_second(20.0).burst(1)),
+                ("/search/v2".to_string(), RateLimit::per_second(1000.0)),
+            ]),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        limiter.acquire("/contents").await;
+        limiter.acquire("/search/v2").await;
+        limiter.acquire("/search/v2").await;
+        assert!(start.elapsed() < Duration::from_millis(40));
+
+        limiter.acquire("/search").await;
+        limiter.acquire("/search").await;
+        limiter.acquire("/search").await;
+        assert!(start.elapsed() >= Duration::from_millis(95));
+    }
+}
@@ -0,0 +1,405 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::Stream;
+use tokio::time::Instant;
+
+use crate::{
+    pagination::{paginate, ListParams, Page},
+    path_segment, Exa, ExaError,
+};
+
+impl Exa {
+    /// Returns a client for Exa's asynchronous research tasks.
+    pub fn research(&self) -> Research<'_> {
+        Research { exa: self }
+    }
+}
+
+/// Client for the `/research/v1` endpoints, obtained through `Exa::research`.
+///
+/// Research tasks run asynchronously: `create` returns immediately with a pending task, whose
+/// progress can be checked with `get` or awaited with `poll_until_finished`.
+pub struct Research<'a> {
+    exa: &'a Exa,
+}
+
+impl<'a> Research<'a> {
+    /// Creates a new research task.
+    ///
+    /// Server errors aren't retried, since the task may have been created anyway; see
+    /// `RetryPolicy`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use std::time::Duration;
+    /// # use exa_sdk::{Exa, ResearchRequest};
+    /// # use anyhow::Result;
+    /// #
+    /// # async fn example() -> Result<()> {
+    /// # let exa = Exa::builder().api_key("your_api_key".into()).build()?;
+    /// let task = exa
+    ///     .research()
+    ///     .create(ResearchRequest::new("Summarise recent Rust async runtime releases"))
+    ///     .await?;
+    ///
+    /// let task = exa
+    ///     .research()
+    ///     .poll_until_finished(&task.id, Duration::from_secs(5), Duration::from_secs(600))
+    ///     .await?;
+    /// println!("{:?}", task.output);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create(&self, request: ResearchRequest) -> Result<ResearchTask, ExaError> {
+        self.exa.post_non_idempotent("/research/v1", request).await
+    }
+
+    /// Fetches a research task by id.
+    pub async fn get(&self, id: &str) -> Result<ResearchTask, ExaError> {
+        self.exa
+            .get(format!("/research/v1/{}", path_segment(id)), &())
+            .await
+    }
+
+    /// Lists research tasks, one page at a time.
+    pub async fn list(&self, params: ListParams) -> Result<Page<ResearchTask>, ExaError> {
+        self.exa.get("/research/v1", &params).await
+    }
+
+    /// Lists all research tasks, fetching pages of `limit` items as the stream is consumed.
+    pub fn list_all(
+        &self,
+        limit: Option<u32>,
+    ) -> impl Stream<Item = Result<ResearchTask, ExaError>> + 'a {
+        let exa = self.exa;
+        paginate(limit, move |params| async move {
+            exa.research().list(params).await
+        })
+    }
+
+    /// Polls a research task every `interval` until it completes, fails or is canceled.
+    ///
+    /// Returns `ExaError::ResearchTimeout` if the task is still running after `timeout`.
+    pub async fn poll_until_finished(
+        &self,
+        id: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<ResearchTask, ExaError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let task = self.get(id).await?;
+            if task.status.is_finished() {
+                return Ok(task);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(ExaError::ResearchTimeout {
+                    id: id.to_string(),
+                    timeout,
+                });
+            }
+            tokio::time::sleep(interval.min(deadline - now)).await;
+        }
+    }
+}
+
+/// Represents a request to create a research task
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct ResearchRequest {
+    /// What the research task should find out
+    pub instructions: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<ResearchModel>,
+    /// JSON schema the output should conform to
+    #[serde(skip_serializing_if = "Option::is_none", rename = "outputSchema")]
+    pub output_schema: Option<serde_json::Value>,
+}
+
+impl ResearchRequest {
+    pub fn new(instructions: impl Into<String>) -> Self {
+        Self {
+            instructions: instructions.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResearchModel {
+    ExaResearch,
+    ExaResearchPro,
+}
+
+/// Represents a research task and, once finished, its output
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct ResearchTask {
+    #[serde(rename = "researchId")]
+    pub id: String,
+    pub status: ResearchStatus,
+    pub instructions: String,
+    pub model: Option<ResearchModel>,
+    #[serde(rename = "outputSchema")]
+    pub output_schema: Option<serde_json::Value>,
+    /// Creation time, as milliseconds since the Unix epoch
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<i64>,
+    /// Completion time, as milliseconds since the Unix epoch
+    #[serde(rename = "finishedAt")]
+    pub finished_at: Option<i64>,
+    /// Output of the task, available once it has completed
+    pub output: Option<ResearchOutput>,
+    /// Reason of the failure, when the task has failed
+    pub error: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct ResearchOutput {
+    /// Output of the research task as text
+    pub content: String,
+    /// Output parsed according to `output_schema`, when one was given
+    pub parsed: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResearchStatus {
+    Pending,
+    Running,
+    Completed,
+    Canceled,
+    Failed,
+    /// A status not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+impl ResearchStatus {
+    /// Whether the task has stopped running, successfully or not
+    ///
+    /// `Unknown` isn't considered finished, so `poll_until_finished` keeps polling through a
+    /// new status until it reaches a known final one or times out.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            ResearchStatus::Completed | ResearchStatus::Canceled | ResearchStatus::Failed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use futures::TryStreamExt;
+    use mockito::{Matcher, Server as MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn setup(base_url: String) -> Result<Exa> {
+        Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(base_url)
+            .build()
+    }
+
+    fn task(id: &str, status: &str) -> serde_json::Value {
+        json!({
+            "researchId": id,
+            "status": status,
+            "instructions": "Test instructions",
+            "model": "exa-research",
+            "createdAt": 1700000000000i64
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_research() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/research/v1")
+            .match_body(Matcher::Json(json!({
+                "instructions": "Test instructions",
+                "model": "exa-research-pro",
+                "outputSchema": {"type": "object"}
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(task("r_1", "pending").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let request = ResearchRequest {
+            model: Some(ResearchModel::ExaResearchPro),
+            output_schema: Some(json!({"type": "object"})),
+            ..ResearchRequest::new("Test instructions")
+        };
+
+        let task = exa.research().create(request).await?;
+
+        assert_eq!(task.id, "r_1");
+        assert_eq!(task.status, ResearchStatus::Pending);
+        assert_eq!(task.created_at, Some(1700000000000));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_all_research_follows_cursor() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _first = server
+            .mock("GET", "/research/v1")
+            .match_query(Matcher::UrlEncoded("limit".into(), "1".into()))
+            .with_status(200)
+            .with_body(
+                json!({"data": [task("r_1", "completed")], "hasMore": true, "nextCursor": "c_1"})
+                    .to_string(),
+            )
+            .create();
+        let _second = server
+            .mock("GET", "/research/v1")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("limit".into(), "1".into()),
+                Matcher::UrlEncoded("cursor".into(), "c_1".into()),
+            ]))
+            .with_status(200)
+            .with_body(
+                json!({"data": [task("r_2", "running")], "hasMore": false, "nextCursor": null})
+                    .to_string(),
+            )
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let tasks = exa
+            .research()
+            .list_all(Some(1))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| task.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["r_1", "r_2"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_status() -> Result<()> {
+        let task: ResearchTask = serde_json::from_value(task("r_1", "queued"))?;
+        assert_eq!(task.status, ResearchStatus::Unknown);
+        assert!(!task.status.is_finished());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_is_not_retried_on_server_errors() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock("POST", "/research/v1")
+            .with_status(502)
+            .with_body("Bad Gateway")
+            .expect(1)
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let result = exa
+            .research()
+            .create(ResearchRequest::new("Test instructions"))
+            .await;
+
+        m.assert();
+        assert!(matches!(result, Err(ExaError::ServerError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_escapes_the_id() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock("GET", "/research/v1/r_1%2Fcancel%3Fx%3D1%23top")
+            .with_status(200)
+            .with_body(task("r_1", "running").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        exa.research().get("r_1/cancel?x=1#top").await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_finished() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let running = server
+            .mock("GET", "/research/v1/r_1")
+            .with_status(200)
+            .with_body(task("r_1", "running").to_string())
+            .expect(1)
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let mut completed = task("r_1", "completed");
+        completed["output"] = json!({"content": "Test output"});
+        let _completed = server
+            .mock("GET", "/research/v1/r_1")
+            .with_status(200)
+            .with_body(completed.to_string())
+            .create();
+
+        let task = exa
+            .research()
+            .poll_until_finished("r_1", Duration::from_millis(1), Duration::from_secs(5))
+            .await?;
+
+        running.assert();
+        assert_eq!(task.status, ResearchStatus::Completed);
+        assert_eq!(task.output.unwrap().content, "Test output");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_finished_timeout() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("GET", "/research/v1/r_1")
+            .with_status(200)
+            .with_body(task("r_1", "running").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let result = exa
+            .research()
+            .poll_until_finished("r_1", Duration::from_millis(5), Duration::from_millis(20))
+            .await;
+
+        assert!(matches!(result, Err(ExaError::ResearchTimeout { id, .. }) if id == "r_1"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+use crate::{transport::TransportError, ExaError};
+
+/// Controls how `Exa` retries requests that fail with a transient error.
+///
+/// A request is retried when the server answers with one of `retryable_statuses`, or when the
+/// connection fails or times out before a response is received. Between attempts the client
+/// waits for an exponentially growing delay, unless the server sent a `Retry-After` header, in
+/// which case that delay is honoured instead, as long as it isn't longer than `max_delay`.
+///
+/// Requests creating a paid job, such as a research task, a webset or an enrichment, are only
+/// retried when the API can't have processed them: when the connection couldn't be established,
+/// or when the server answered 429. A 5xx or a dropped connection may come after the job was
+/// created, and retrying would create and bill a second one.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on every subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound for the delay between two attempts.
+    ///
+    /// Caps the computed backoff. When the server asks for a longer wait with `Retry-After`,
+    /// the request isn't retried: its error is returned right away, with the requested delay
+    /// in `HttpError::retry_after`, so the caller can decide whether to wait that long.
+    pub max_delay: Duration,
+    /// Whether to randomise the backoff delay to avoid synchronised retries.
+    pub jitter: bool,
+    /// HTTP status codes considered transient.
+    pub retryable_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable_statuses: vec![429, 500, 502, 503, 504],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Decides whether to retry after attempt number `attempt` (1-based), given the status and
+    /// headers of the response it got or the error that prevented getting one.
+    ///
+    /// Requests that aren't `idempotent` are only retried if they can't have been processed.
+    /// Returns how long to wait before the next attempt, or `None` to give up and return the
+    /// outcome of this one.
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u32,
+        idempotent: bool,
+        outcome: Result<(StatusCode, &HeaderMap), &TransportError>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retry_after = match outcome {
+            Ok((status, headers))
+                if self.is_retryable_status(status)
+                    && (idempotent || status == StatusCode::TOO_MANY_REQUESTS) =>
+            {
+                retry_after(headers)
+            }
+            Err(error) if self.is_retryable_error(error) && (idempotent || error.is_connect()) => {
+                None
+            }
+            _ => return None,
+        };
+
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status.as_u16())
+    }
+
+    fn is_retryable_error(&self, error: &TransportError) -> bool {
+        error.is_connect() || error.is_timeout() || error.is_request()
+    }
+
+    /// Returns the delay to wait before the attempt following `attempt` (1-based).
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter && !delay.is_zero() {
+            let millis = delay.as_millis() as u64;
+            Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+        } else {
+            delay
+        }
+    }
+}
+
+/// Wraps the error of the last attempt in `ExaError::RetriesExhausted` if it was retried.
+pub(crate) fn exhausted(attempts: u32, last: ExaError) -> ExaError {
+    if attempts > 1 {
+        ExaError::RetriesExhausted {
+            attempts,
+            last: Box::new(last),
+        }
+    } else {
+        last
+    }
+}
+
+/// Parses a `Retry-After` header, given either as delay seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use mockito::Server as MockServer;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{transport::TransportErrorKind, Exa, ExaError, SearchRequest};
+
+    fn setup(base_url: String, policy: RetryPolicy) -> Result<Exa> {
+        Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(base_url)
+            .retry_policy(policy)
+            .build()
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+            jitter: false,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_retry_delay_decision() {
+        let policy = fast_policy();
+        let headers = HeaderMap::new();
+        let mut retry_after = HeaderMap::new();
+        retry_after.insert(RETRY_AFTER, HeaderValue::from_static("0"));
+
+        assert_eq!(
+            policy.retry_delay(1, true, Ok((StatusCode::SERVICE_UNAVAILABLE, &headers))),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            policy.retry_delay(2, true, Ok((StatusCode::TOO_MANY_REQUESTS, &retry_after))),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            policy.retry_delay(1, true, Ok((StatusCode::OK, &headers))),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(1, true, Ok((StatusCode::BAD_REQUEST, &headers))),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(3, true, Ok((StatusCode::SERVICE_UNAVAILABLE, &headers))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_non_idempotent_requests_are_only_retried_if_not_processed() {
+        let policy = fast_policy();
+        let headers = HeaderMap::new();
+
+        assert_eq!(
+            policy.retry_delay(1, false, Ok((StatusCode::BAD_GATEWAY, &headers))),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(1, false, Ok((StatusCode::TOO_MANY_REQUESTS, &headers))),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            policy.retry_delay(
+                1,
+                false,
+                Err(&TransportError::new(TransportErrorKind::Request, "reset"))
+            ),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(
+                1,
+                false,
+                Err(&TransportError::new(TransportErrorKind::Connect, "refused"))
+            ),
+            Some(Duration::from_millis(1))
+        );
+    }
+
+    #[test]
+    fn test_retry_after_longer_than_max_delay_is_not_waited_for() {
+        let policy = fast_policy();
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("86400"));
+
+        assert_eq!(
+            policy.retry_delay(1, true, Ok((StatusCode::TOO_MANY_REQUESTS, &headers))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_status_until_success() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let failing = server
+            .mock("POST", "/search")
+            .with_status(503)
+            .with_header("retry-after", "0")
+            .with_body(json!({"code": "unavailable", "message": "Try again"}).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let exa = setup(server.url(), fast_policy())?;
+
+        // The mock above only answers twice; register the successful one behind it.
+        let succeeding = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": []}).to_string())
+            .create_async()
+            .await;
+
+        let response = exa
+            .search(SearchRequest {
+                query: "test query".to_string(),
+                ..Default::default()
+            })
+            .await?;
+
+        assert!(response.results.is_empty());
+        failing.assert_async().await;
+        succeeding.assert_async().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_returns_immediately() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let mock = server
+            .mock("POST", "/search")
+            .with_status(429)
+            .with_header("retry-after", "86400")
+            .with_body(json!({"code": "rate_limited", "message": "Slow down"}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let exa = setup(server.url(), fast_policy())?;
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            exa.search(SearchRequest {
+                query: "test query".to_string(),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+        mock.assert_async().await;
+        match result {
+            Err(ExaError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(86400)));
+            }
+            other => panic!("Expected RateLimited, got {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reports_attempts_when_retries_are_exhausted() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let mock = server
+            .mock("POST", "/search")
+            .with_status(429)
+            .with_body(json!({"code": "rate_limited", "message": "Slow down"}).to_string())
+            .expect(3)
+            .create_async()
+            .await;
+
+        let exa = setup(server.url(), fast_policy())?;
+
+        let result = exa
+            .search(SearchRequest {
+                query: "test query".to_string(),
+                ..Default::default()
+            })
+            .await;
+
+        mock.assert_async().await;
+        match result {
+            Err(ExaError::RetriesExhausted { attempts, last }) => {
+                assert_eq!(attempts, 3);
+                assert_eq!(
+                    last.to_string(),
+                    "Rate limited: 429 - rate_limited - Slow down"
+                );
+            }
+            other => panic!("Expected RetriesExhausted, got {:?}", other.err()),
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
+use futures::{stream, Stream, StreamExt};
 
-use crate::{Exa, ExaError};
+use crate::{cost::CostDollars, Exa, ExaError};
 
 impl Exa {
     /// Performs a search request to the Exa API.
@@ -48,6 +49,27 @@ impl Exa {
     pub async fn search(&self, request: SearchRequest) -> Result<SearchResponse, ExaError> {
         self.post("/search", request).await
     }
+
+    /// Runs many searches, at most `concurrency` at a time.
+    ///
+    /// Responses are yielded as they complete, which isn't necessarily the order of `requests`,
+    /// so each one comes with the index of its request. A failed search doesn't stop the others.
+    pub fn search_many<I>(
+        &self,
+        requests: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = (usize, Result<SearchResponse, ExaError>)>
+    where
+        I: IntoIterator<Item = SearchRequest>,
+    {
+        let exa = self.clone();
+        stream::iter(requests.into_iter().enumerate())
+            .map(move |(index, request)| {
+                let exa = exa.clone();
+                async move { (index, exa.search(request).await) }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
 }
 
 /// Represents the response from a search request to the Exa API
@@ -61,6 +83,15 @@ pub struct SearchResponse {
     /// If applicable, the date filter intelligently inferred from input queries that have autopropmpt on.
     #[serde(rename = "autoDate")]
     pub auto_date: Option<String>,
+    /// The search type `SearchKind::Auto` resolved to
+    #[serde(rename = "resolvedSearchType")]
+    pub resolved_search_type: Option<ResolvedSearchType>,
+    /// Identifier of the request, useful when contacting support
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// Cost of the request
+    #[serde(rename = "costDollars")]
+    pub cost_dollars: Option<CostDollars>,
 }
 
 /// Represents a single search result from the Exa API
@@ -82,10 +113,32 @@ pub struct SearchResult {
     pub highlights: Option<Vec<String>>,
     #[serde(rename = "highlightScores")]
     pub highlight_scores: Option<Vec<f64>>,
+    /// Summary of the page, when requested through `SearchContent::summary`
+    pub summary: Option<Summary>,
+    /// URL of the main image of the result, if any
+    pub image: Option<String>,
+    /// URL of the favicon of the result's domain, if any
+    pub favicon: Option<String>,
+    /// Links found on the page, when requested through `SearchContent::extras`
+    pub extras: Option<SearchResultExtras>,
+    /// Subpages of the result crawled alongside it, when requested
+    #[serde(default)]
+    pub subpages: Vec<SearchResult>,
+}
+
+/// Links extracted from a result page
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct SearchResultExtras {
+    /// Outbound links found on the page
+    #[serde(default)]
+    pub links: Vec<String>,
+    /// Image links found on the page
+    #[serde(default, rename = "imageLinks")]
+    pub image_links: Vec<String>,
 }
 
 /// Represents a search request to the Exa API
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SearchRequest {
     /// The search query string
     pub query: String,
@@ -94,6 +147,9 @@ pub struct SearchRequest {
     pub use_autoprompt: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     pub kind: Option<SearchKind>,
+    /// Restricts results to a type of content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<SearchCategory>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "includeText")]
     pub include_text: Option<Vec<String>>,
     /// Number of results to return (default: 10, max: 100)
@@ -118,57 +174,130 @@ pub struct SearchRequest {
     /// Strings to exclude from webpage text (max 1 string, 5 words)
     #[serde(skip_serializing_if = "Option::is_none", rename = "excludeText")]
     pub exclude_text: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contents: Option<SearchContent>,
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SearchContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<SearchContentTextType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub highlights: Option<SearchHighlights>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<SearchSummary>,
+    /// Whether to crawl pages live instead of using cached content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub livecrawl: Option<Livecrawl>,
+    /// Timeout for live crawling, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none", rename = "livecrawlTimeout")]
+    pub livecrawl_timeout: Option<u32>,
+    /// Number of subpages to crawl for each result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subpages: Option<u32>,
+    /// Keywords used to pick which subpages to crawl, e.g. "about" or "pricing"
+    #[serde(skip_serializing_if = "Option::is_none", rename = "subpageTarget")]
+    pub subpage_target: Option<SubpageTarget>,
+    /// Additional data to extract from each page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<SearchExtras>,
+}
+
+/// Represents the extra data to extract from each page
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct SearchExtras {
+    /// Number of outbound links to return per page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<u32>,
+    /// Number of image links to return per page
+    #[serde(skip_serializing_if = "Option::is_none", rename = "imageLinks")]
+    pub image_links: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum SubpageTarget {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Controls when pages are crawled live rather than served from Exa's cache
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Livecrawl {
+    /// Only use cached content
+    Never,
+    /// Crawl live when no cached content is available
+    Fallback,
+    /// Always crawl live
+    Always,
+    /// Crawl live, falling back to cached content if crawling fails
+    Preferred,
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
 #[serde(untagged)]
 pub enum SearchContentTextType {
     Bool(bool),
     Object(SearchContentText),
 }
 
-#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SearchContentText {
     /// Max length in characters for the text returned
-    #[serde(rename = "maxCharacters")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxCharacters")]
     pub max_characters: Option<u32>,
     /// Whether HTML tags, which can help the LLM understand structure of text, should be included. Default false
-    #[serde(rename = "includeHtmlTags")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "includeHtmlTags")]
     pub include_html_tags: Option<bool>,
 }
 
 /// Represents the highlights configuration for search results
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SearchHighlights {
     /// The number of sentences to be returned in each snippet. Default 5
-    #[serde(rename = "numSentences")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "numSentences")]
     pub num_sentences: Option<u32>,
     /// The number of snippets to return per page. Default 1
-    #[serde(rename = "highlightsPerUrl")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "highlightsPerUrl")]
     pub highlights_per_url: Option<u32>,
     /// If specified, targets snippets most relevant to the query. In search, defaults to the search query.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<String>,
 }
 
-/// Represents a summary of a webpage
-#[derive(serde::Deserialize, serde::Serialize)]
+/// Represents the summary configuration for search results
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct SearchSummary {
-    /// Summary of the webpage
-    pub summary: String,
     /// If specified, tries to answer the query in the summary
-    #[serde(rename = "query")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "query")]
     pub query: Option<String>,
+    /// JSON schema the summary should conform to, to get structured summaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
 }
 
-#[derive(Clone, serde::Deserialize, serde::Serialize)]
+/// Represents the summary of a webpage returned on a result
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum Summary {
+    /// A plain text summary
+    Text(String),
+    /// A structured summary, returned when a schema was requested
+    Structured(serde_json::Value),
+}
+
+impl Summary {
+    /// Returns the summary as JSON, parsing it if the API returned it as an encoded string.
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        match self {
+            Summary::Text(text) => serde_json::from_str(text),
+            Summary::Structured(value) => Ok(value.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchKind {
     Neural,
@@ -176,18 +305,476 @@ pub enum SearchKind {
     Auto,
 }
 
+/// The search type a request was actually run with, as reported by the API
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResolvedSearchType {
+    Neural,
+    Keyword,
+    /// A search type not known to this version of the SDK
+    Other(String),
+}
+
+impl ResolvedSearchType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResolvedSearchType::Neural => "neural",
+            ResolvedSearchType::Keyword => "keyword",
+            ResolvedSearchType::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<String> for ResolvedSearchType {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "neural" => ResolvedSearchType::Neural,
+            "keyword" => ResolvedSearchType::Keyword,
+            _ => ResolvedSearchType::Other(kind),
+        }
+    }
+}
+
+impl serde::Serialize for ResolvedSearchType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ResolvedSearchType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(ResolvedSearchType::from)
+    }
+}
+
+/// Type of content a search can be restricted to
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SearchCategory {
+    Company,
+    ResearchPaper,
+    News,
+    Pdf,
+    Github,
+    Tweet,
+    PersonalSite,
+    LinkedinProfile,
+    FinancialReport,
+    /// A category not known to this version of the SDK
+    Other(String),
+}
+
+impl SearchCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SearchCategory::Company => "company",
+            SearchCategory::ResearchPaper => "research paper",
+            SearchCategory::News => "news",
+            SearchCategory::Pdf => "pdf",
+            SearchCategory::Github => "github",
+            SearchCategory::Tweet => "tweet",
+            SearchCategory::PersonalSite => "personal site",
+            SearchCategory::LinkedinProfile => "linkedin profile",
+            SearchCategory::FinancialReport => "financial report",
+            SearchCategory::Other(category) => category,
+        }
+    }
+}
+
+impl From<String> for SearchCategory {
+    fn from(category: String) -> Self {
+        match category.as_str() {
+            "company" => SearchCategory::Company,
+            "research paper" => SearchCategory::ResearchPaper,
+            "news" => SearchCategory::News,
+            "pdf" => SearchCategory::Pdf,
+            "github" => SearchCategory::Github,
+            "tweet" => SearchCategory::Tweet,
+            "personal site" => SearchCategory::PersonalSite,
+            "linkedin profile" => SearchCategory::LinkedinProfile,
+            "financial report" => SearchCategory::FinancialReport,
+            _ => SearchCategory::Other(category),
+        }
+    }
+}
+
+impl serde::Serialize for SearchCategory {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SearchCategory {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SearchCategory::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::{CostTracker, FindSimilarResponse, RateLimit};
     use anyhow::Result;
-    use mockito::Server as MockServer;
+    use mockito::{Matcher, Server as MockServer};
     use serde_json::json;
 
     fn setup(base_url: String) -> Result<Exa> {
-        Ok(Exa::builder()
+        Exa::builder()
             .api_key("test_key".to_string())
             .base_url(base_url)
-            .build()?)
+            .build()
+    }
+
+    #[test]
+    fn test_search_request_serialization() -> Result<()> {
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            use_autoprompt: Some(true),
+            kind: Some(SearchKind::Neural),
+            category: Some(SearchCategory::News),
+            include_text: Some(vec!["rust".to_string()]),
+            num_results: Some(5),
+            include_domains: Some(vec!["example.com".to_string()]),
+            exclude_domains: Some(vec!["spam.com".to_string()]),
+            start_crawl_date: Some("2023-01-01".to_string()),
+            end_crawl_date: Some("2023-12-31".to_string()),
+            start_published_date: Some("2022-01-01".to_string()),
+            end_published_date: Some("2022-12-31".to_string()),
+            exclude_text: Some(vec!["ads".to_string()]),
+            contents: Some(SearchContent {
+                text: Some(SearchContentTextType::Object(SearchContentText {
+                    max_characters: Some(1000),
+                    include_html_tags: Some(true),
+                })),
+                highlights: Some(SearchHighlights {
+                    num_sentences: Some(2),
+                    highlights_per_url: Some(3),
+                    query: None,
+                }),
+                ..Default::default()
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "query": "test query",
+                "useAutoprompt": true,
+                "type": "neural",
+                "category": "news",
+                "includeText": ["rust"],
+                "numResults": 5,
+                "includeDomains": ["example.com"],
+                "excludeDomains": ["spam.com"],
+                "startCrawlDate": "2023-01-01",
+                "endCrawlDate": "2023-12-31",
+                "startPublishedDate": "2022-01-01",
+                "endPublishedDate": "2022-12-31",
+                "excludeText": ["ads"],
+                "contents": {
+                    "text": {"maxCharacters": 1000, "includeHtmlTags": true},
+                    "highlights": {"numSentences": 2, "highlightsPerUrl": 3}
+                }
+            })
+        );
+
+        let minimal = SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&minimal)?,
+            json!({"query": "test query"})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_livecrawl_serialization() -> Result<()> {
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            contents: Some(SearchContent {
+                text: Some(SearchContentTextType::Bool(true)),
+                livecrawl: Some(Livecrawl::Always),
+                livecrawl_timeout: Some(5000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({
+                "query": "test query",
+                "contents": {"text": true, "livecrawl": "always", "livecrawlTimeout": 5000}
+            })
+        );
+
+        for (livecrawl, expected) in [
+            (Livecrawl::Never, "never"),
+            (Livecrawl::Fallback, "fallback"),
+            (Livecrawl::Always, "always"),
+            (Livecrawl::Preferred, "preferred"),
+        ] {
+            assert_eq!(serde_json::to_value(livecrawl)?, json!(expected));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_with_subpages() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/search")
+            .match_body(Matcher::Json(json!({
+                "query": "test query",
+                "contents": {"subpages": 2, "subpageTarget": ["about", "pricing"]}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "results": [{
+                        "id": "https://example.com",
+                        "title": "Example",
+                        "url": "https://example.com",
+                        "subpages": [{
+                            "id": "https://example.com/about",
+                            "title": "About",
+                            "url": "https://example.com/about",
+                            "subpages": [{
+                                "id": "https://example.com/about/team",
+                                "title": "Team",
+                                "url": "https://example.com/about/team"
+                            }]
+                        }]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = setup(mock_url)?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            contents: Some(SearchContent {
+                subpages: Some(2),
+                subpage_target: Some(SubpageTarget::Many(vec![
+                    "about".to_string(),
+                    "pricing".to_string(),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let response = exa.search(request).await?;
+
+        let subpage = &response.results[0].subpages[0];
+        assert_eq!(subpage.url, "https://example.com/about");
+        assert_eq!(subpage.subpages[0].title, "Team");
+        assert!(subpage.subpages[0].subpages.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_with_extras() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/search")
+            .match_body(Matcher::Json(json!({
+                "query": "test query",
+                "contents": {"extras": {"links": 2, "imageLinks": 1}}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "results": [{
+                        "id": "https://example.com",
+                        "title": "Example",
+                        "url": "https://example.com",
+                        "image": "https://example.com/og.png",
+                        "favicon": "https://example.com/favicon.ico",
+                        "extras": {
+                            "links": ["https://example.org", "https://example.net"],
+                            "imageLinks": ["https://example.com/logo.png"]
+                        }
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = setup(mock_url)?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            contents: Some(SearchContent {
+                extras: Some(SearchExtras {
+                    links: Some(2),
+                    image_links: Some(1),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let response = exa.search(request).await?;
+
+        let result = &response.results[0];
+        assert_eq!(result.image, Some("https://example.com/og.png".to_string()));
+        assert_eq!(
+            result.favicon,
+            Some("https://example.com/favicon.ico".to_string())
+        );
+        let extras = result.extras.as_ref().unwrap();
+        assert_eq!(extras.links.len(), 2);
+        assert_eq!(extras.image_links, vec!["https://example.com/logo.png"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_with_summary() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let schema = json!({
+            "type": "object",
+            "properties": {"founded": {"type": "number"}}
+        });
+
+        let _m = server
+            .mock("POST", "/search")
+            .match_body(Matcher::Json(json!({
+                "query": "test query",
+                "contents": {"summary": {"query": "When was it founded?", "schema": schema}}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "results": [
+                        {
+                            "id": "1",
+                            "title": "Plain",
+                            "url": "https://example.com",
+                            "summary": "Founded in 2020."
+                        },
+                        {
+                            "id": "2",
+                            "title": "Structured",
+                            "url": "https://example.org",
+                            "summary": {"founded": 2020}
+                        },
+                        {
+                            "id": "3",
+                            "title": "Encoded",
+                            "url": "https://example.net",
+                            "summary": "{\"founded\": 2020}"
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = setup(mock_url)?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            contents: Some(SearchContent {
+                summary: Some(SearchSummary {
+                    query: Some("When was it founded?".to_string()),
+                    schema: Some(schema),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let response = exa.search(request).await?;
+
+        assert_eq!(
+            response.results[0].summary,
+            Some(Summary::Text("Founded in 2020.".to_string()))
+        );
+        assert_eq!(
+            response.results[1].summary,
+            Some(Summary::Structured(json!({"founded": 2020})))
+        );
+        assert_eq!(
+            response.results[2].summary.as_ref().unwrap().to_json()?,
+            json!({"founded": 2020})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_resolved_search_type() -> Result<()> {
+        let response: SearchResponse = serde_json::from_value(json!({
+            "results": [],
+            "resolvedSearchType": "hybrid"
+        }))?;
+        assert_eq!(
+            response.resolved_search_type,
+            Some(ResolvedSearchType::Other("hybrid".to_string()))
+        );
+
+        let response: FindSimilarResponse = serde_json::from_value(json!({
+            "results": [],
+            "resolvedSearchType": "keyword"
+        }))?;
+        assert_eq!(
+            response.resolved_search_type,
+            Some(ResolvedSearchType::Keyword)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_category_serialization() -> Result<()> {
+        for (category, expected) in [
+            (SearchCategory::Company, "company"),
+            (SearchCategory::ResearchPaper, "research paper"),
+            (SearchCategory::News, "news"),
+            (SearchCategory::Pdf, "pdf"),
+            (SearchCategory::Github, "github"),
+            (SearchCategory::Tweet, "tweet"),
+            (SearchCategory::PersonalSite, "personal site"),
+            (SearchCategory::LinkedinProfile, "linkedin profile"),
+            (SearchCategory::FinancialReport, "financial report"),
+            (SearchCategory::Other("movie".to_string()), "movie"),
+        ] {
+            assert_eq!(serde_json::to_value(&category)?, json!(expected));
+            assert_eq!(
+                serde_json::from_value::<SearchCategory>(json!(expected))?,
+                category
+            );
+        }
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            category: Some(SearchCategory::ResearchPaper),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({"query": "test query", "category": "research paper"})
+        );
+
+        Ok(())
     }
 
     #[tokio::test]
@@ -276,6 +863,220 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_search_response_metadata() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "requestId": "req_123",
+                    "resolvedSearchType": "neural",
+                    "results": [],
+                    "costDollars": {
+                        "total": 0.006,
+                        "breakDown": [{
+                            "search": 0.005,
+                            "contents": 0.001,
+                            "breakdown": {
+                                "keywordSearch": 0,
+                                "neuralSearch": 0.005,
+                                "contentText": 0.001,
+                                "contentHighlight": 0,
+                                "contentSummary": 0
+                            }
+                        }],
+                        "perRequestPrices": {"neuralSearch_1_25_results": 0.005},
+                        "perPagePrices": {"contentText": 0.001}
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = setup(mock_url)?;
+
+        let response = exa
+            .search(SearchRequest {
+                query: "test query".to_string(),
+                kind: Some(SearchKind::Auto),
+                ..Default::default()
+            })
+            .await?;
+
+        assert_eq!(response.request_id, Some("req_123".to_string()));
+        assert_eq!(
+            response.resolved_search_type,
+            Some(ResolvedSearchType::Neural)
+        );
+        let cost = response.cost_dollars.unwrap();
+        assert_eq!(cost.total, 0.006);
+        assert_eq!(cost.search(), 0.005);
+        assert_eq!(cost.contents(), 0.001);
+        assert_eq!(
+            cost.break_down[0].breakdown.as_ref().unwrap().neural_search,
+            Some(0.005)
+        );
+        assert_eq!(cost.per_page_prices["contentText"], 0.001);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_enforces_budget() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "costDollars": {"total": 0.005}}).to_string())
+            .expect(2)
+            .create();
+
+        let tracker = Arc::new(CostTracker::with_budget(0.01));
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .cost_tracker(tracker.clone())
+            .cost_tag("agent")
+            .build()?;
+
+        let request = || SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            exa.search(request()).await?;
+        }
+        let result = exa.search(request()).await;
+
+        m.assert();
+        assert!(matches!(result, Err(ExaError::BudgetExceeded { .. })));
+        let summary = tracker.summary();
+        assert_eq!(summary.requests, 2);
+        assert_eq!(summary.by_endpoint["/search"], 0.01);
+        assert_eq!(summary.by_tag["agent"], 0.01);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_budget_overspend_is_bounded_by_concurrency() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "costDollars": {"total": 0.005}}).to_string())
+            .expect(4)
+            .create();
+
+        let tracker = Arc::new(CostTracker::with_budget(0.005));
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .cost_tracker(tracker.clone())
+            .build()?;
+
+        let request = || SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+        // All four requests are sent before any of them reports its cost.
+        let responses = exa
+            .search_many((0..4).map(|_| request()), 4)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(responses.iter().all(|(_, response)| response.is_ok()));
+        assert!((tracker.total() - 0.02).abs() < 1e-9);
+
+        let result = exa.search(request()).await;
+        assert!(matches!(result, Err(ExaError::BudgetExceeded { .. })));
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_rate_limit_is_shared_by_clones() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": []}).to_string())
+            .expect(3)
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .endpoint_rate_limit("/search", RateLimit::per_second(20.0).burst(1))
+            .build()?;
+
+        let start = std::time::Instant::now();
+        let searches = (0..3).map(|_| {
+            let exa = exa.clone();
+            tokio::spawn(async move {
+                exa.search(SearchRequest {
+                    query: "test query".to_string(),
+                    ..Default::default()
+                })
+                .await
+            })
+        });
+        for search in futures::future::join_all(searches).await {
+            search??;
+        }
+
+        assert!(start.elapsed() >= std::time::Duration::from_millis(95));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_many() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _ok = server
+            .mock("POST", "/search")
+            .match_body(Matcher::PartialJson(json!({"query": "good"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": []}).to_string())
+            .expect(2)
+            .create();
+        let _bad = server
+            .mock("POST", "/search")
+            .match_body(Matcher::PartialJson(json!({"query": "bad"})))
+            .with_status(400)
+            .with_body(json!({"code": "bad_request", "message": "Invalid query"}).to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let requests = ["good", "bad", "good"].map(|query| SearchRequest {
+            query: query.to_string(),
+            ..Default::default()
+        });
+        let mut responses = exa.search_many(requests, 2).collect::<Vec<_>>().await;
+        responses.sort_by_key(|(index, _)| *index);
+
+        assert_eq!(responses.len(), 3);
+        assert!(responses[0].1.is_ok());
+        assert!(matches!(responses[1].1, Err(ExaError::InvalidRequest(_))));
+        assert!(responses[2].1.is_ok());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_search_api_error() -> Result<()> {
         let mut server = MockServer::new_async().await;
@@ -308,8 +1109,96 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "HTTP error: 401 - unauthorized - Your request was unauthorized"
+            "Unauthorized: 401 - unauthorized - Your request was unauthorized"
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_search_decode_error() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"unexpected": true}).to_string())
+            .create();
+
+        let exa = setup(mock_url)?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+
+        match exa.search(request).await {
+            Err(ExaError::Decode { body, .. }) => {
+                assert_eq!(body, json!({"unexpected": true}).to_string())
+            }
+            other => panic!("Expected Decode, got {:?}", other.err()),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_non_json_error_body() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server
+            .mock("POST", "/search")
+            .with_status(502)
+            .with_header("content-type", "text/html")
+            .with_header("x-request-id", "req_123")
+            .with_body("<html><body>Bad Gateway</body></html>")
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .retry_policy(crate::RetryPolicy::none())
+            .build()?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+
+        match exa.search(request).await {
+            Err(ExaError::ServerError(error)) => {
+                assert_eq!(error.status, 502);
+                assert!(error.payload.is_none());
+                assert_eq!(error.body, "<html><body>Bad Gateway</body></html>");
+                assert_eq!(error.request_id, Some("req_123".to_string()));
+            }
+            other => panic!("Expected ServerError, got {:?}", other.err()),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_empty_error_body() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+        let mock_url = server.url();
+
+        let _m = server.mock("POST", "/search").with_status(504).create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(mock_url)
+            .retry_policy(crate::RetryPolicy::none())
+            .build()?;
+
+        let request = SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+
+        let result = exa.search(request).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Server error: 504");
+        Ok(())
+    }
 }
@@ -0,0 +1,275 @@
+//! `tower::Service` implementation for `Exa`, enabled by the `tower` feature.
+//!
+//! This lets the client sit behind tower layers such as timeouts, concurrency limits or load
+//! shedding:
+//!
+//! ```rust,ignore
+//! # use exa_sdk::{Exa, ExaRequest, SearchRequest};
+//! # use tower::{ServiceBuilder, ServiceExt};
+//! # async fn example(exa: Exa) -> anyhow::Result<()> {
+//! let service = ServiceBuilder::new().concurrency_limit(8).service(exa);
+//! let response = service
+//!     .oneshot(ExaRequest::Search(SearchRequest {
+//!         query: "Rust programming".to_string(),
+//!         ..Default::default()
+//!     }))
+//!     .await?
+//!     .into_search();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `ExaRequest` is `Clone`, so the service can also be wrapped in `tower::retry`. The client's
+//! own retry policy still runs inside every call, so each tower attempt may itself retry
+//! transient failures; build the client with `RetryPolicy::none()` to leave retries to tower
+//! alone.
+
+use std::task::{Context, Poll};
+
+use futures::{future::BoxFuture, FutureExt};
+use tower_service::Service;
+
+use crate::{
+    ContentsRequest, ContentsResponse, Exa, ExaError, FindSimilarRequest, FindSimilarResponse,
+    SearchRequest, SearchResponse,
+};
+
+/// A request `Exa` can handle as a `tower::Service`
+#[derive(Clone)]
+pub enum ExaRequest {
+    Search(SearchRequest),
+    FindSimilar(FindSimilarRequest),
+    Contents(ContentsRequest),
+}
+
+/// The response to an `ExaRequest`, of the same variant as the request
+#[derive(Debug)]
+pub enum ExaResponse {
+    Search(SearchResponse),
+    FindSimilar(FindSimilarResponse),
+    Contents(ContentsResponse),
+}
+
+impl ExaResponse {
+    pub fn into_search(self) -> Option<SearchResponse> {
+        match self {
+            ExaResponse::Search(response) => Some(response),
+            _ => None,
+        }
+    }
+
+    pub fn into_find_similar(self) -> Option<FindSimilarResponse> {
+        match self {
+            ExaResponse::FindSimilar(response) => Some(response),
+            _ => None,
+        }
+    }
+
+    pub fn into_contents(self) -> Option<ContentsResponse> {
+        match self {
+            ExaResponse::Contents(response) => Some(response),
+            _ => None,
+        }
+    }
+}
+
+impl From<SearchRequest> for ExaRequest {
+    fn from(request: SearchRequest) -> Self {
+        ExaRequest::Search(request)
+    }
+}
+
+impl From<FindSimilarRequest> for ExaRequest {
+    fn from(request: FindSimilarRequest) -> Self {
+        ExaRequest::FindSimilar(request)
+    }
+}
+
+impl From<ContentsRequest> for ExaRequest {
+    fn from(request: ContentsRequest) -> Self {
+        ExaRequest::Contents(request)
+    }
+}
+
+/// The client is always ready: rate limiting, if configured, happens once a request is called.
+impl Service<ExaRequest> for Exa {
+    type Response = ExaResponse;
+    type Error = ExaError;
+    type Future = BoxFuture<'static, Result<ExaResponse, ExaError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), ExaError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ExaRequest) -> Self::Future {
+        let exa = self.clone();
+        async move {
+            match request {
+                ExaRequest::Search(request) => exa.search(request).await.map(ExaResponse::Search),
+                ExaRequest::FindSimilar(request) => exa
+                    .find_similar(request)
+                    .await
+                    .map(ExaResponse::FindSimilar),
+                ExaRequest::Contents(request) => {
+                    exa.get_contents(request).await.map(ExaResponse::Contents)
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{self, Ready};
+
+    use anyhow::Result;
+    use mockito::Server as MockServer;
+    use serde_json::json;
+    use tower::{retry::Policy, ServiceBuilder, ServiceExt};
+
+    use super::*;
+    use crate::RetryPolicy;
+
+    /// Retries retryable errors up to the given number of times
+    #[derive(Clone)]
+    struct Attempts(usize);
+
+    impl Policy<ExaRequest, ExaResponse, ExaError> for Attempts {
+        type Future = Ready<()>;
+
+        fn retry(
+            &mut self,
+            _request: &mut ExaRequest,
+            result: &mut Result<ExaResponse, ExaError>,
+        ) -> Option<Self::Future> {
+            match result {
+                Err(error) if error.is_retryable() && self.0 > 0 => {
+                    self.0 -= 1;
+                    Some(future::ready(()))
+                }
+                _ => None,
+            }
+        }
+
+        fn clone_request(&mut self, request: &ExaRequest) -> Option<ExaRequest> {
+            Some(request.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exa_as_tower_service() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _search = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_body(json!({"results": [], "requestId": "req_search"}).to_string())
+            .create();
+        let _contents = server
+            .mock("POST", "/contents")
+            .with_status(200)
+            .with_body(json!({"results": [], "requestId": "req_contents"}).to_string())
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .build()?;
+        let service = ServiceBuilder::new().concurrency_limit(1).service(exa);
+
+        let response = service
+            .clone()
+            .oneshot(
+                SearchRequest {
+                    query: "test query".to_string(),
+                    ..Default::default()
+                }
+                .into(),
+            )
+            .await?
+            .into_search()
+            .unwrap();
+        assert_eq!(response.request_id, Some("req_search".to_string()));
+
+        let response = service
+            .oneshot(ContentsRequest::new(vec!["test_id".to_string()]).into())
+            .await?;
+        assert!(matches!(
+            response,
+            ExaResponse::Contents(ContentsResponse { request_id: Some(id), .. }) if id == "req_contents"
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exa_under_tower_retry() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let failing = server
+            .mock("POST", "/search")
+            .with_status(503)
+            .with_body(json!({"code": "unavailable", "message": "Try again"}).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .retry_policy(RetryPolicy::none())
+            .build()?;
+
+        let succeeding = server
+            .mock("POST", "/search")
+            .with_status(200)
+            .with_body(json!({"results": [], "requestId": "req_retried"}).to_string())
+            .create_async()
+            .await;
+
+        let response = ServiceBuilder::new()
+            .retry(Attempts(2))
+            .service(exa)
+            .oneshot(ExaRequest::Search(SearchRequest {
+                query: "test query".to_string(),
+                ..Default::default()
+            }))
+            .await?
+            .into_search()
+            .unwrap();
+
+        failing.assert_async().await;
+        succeeding.assert_async().await;
+        assert_eq!(response.request_id, Some("req_retried".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_service_errors_are_exa_errors() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/findSimilar")
+            .with_status(401)
+            .with_body(json!({"code": "unauthorized", "message": "Invalid API key"}).to_string())
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .build()?;
+
+        let result = exa
+            .oneshot(ExaRequest::FindSimilar(FindSimilarRequest {
+                url: "https://example.com".to_string(),
+                ..Default::default()
+            }))
+            .await;
+
+        assert!(matches!(result, Err(ExaError::Unauthorized(_))));
+
+        Ok(())
+    }
+}
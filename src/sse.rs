@@ -0,0 +1,123 @@
+//! Incremental decoder for `text/event-stream` bodies.
+
+/// A single server-sent event.
+#[derive(Debug, PartialEq)]
+pub(crate) struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+/// Splits a byte stream into server-sent events.
+///
+/// Chunks may end anywhere, including in the middle of a line or of a UTF-8 sequence, so
+/// incomplete lines are buffered until the rest of them arrives.
+#[derive(Default)]
+pub(crate) struct SseDecoder {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Feeds a chunk of bytes and returns the events it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let mut line = self.buffer.drain(..=position).collect::<Vec<_>>();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+
+            if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flushes the event left pending when the stream ends without a trailing blank line.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if self.data.is_empty() {
+            self.event = None;
+            return None;
+        }
+
+        Some(SseEvent {
+            event: self.event.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(decoder.push(b"data: {\"a\":").is_empty());
+        assert_eq!(
+            decoder.push(b"1}\r\n\r\n: keep-alive\n\nevent: done\ndata: x\ndata: y\n\n"),
+            vec![
+                SseEvent {
+                    event: None,
+                    data: "{\"a\":1}".to_string(),
+                },
+                SseEvent {
+                    event: Some("done".to_string()),
+                    data: "x\ny".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish_flushes_pending_event() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(decoder.push(b"data: [DONE]").is_empty());
+        assert_eq!(
+            decoder.finish(),
+            Some(SseEvent {
+                event: None,
+                data: "[DONE]".to_string(),
+            })
+        );
+        assert_eq!(decoder.finish(), None);
+    }
+}
@@ -0,0 +1,542 @@
+//! The HTTP layer `Exa` sends its requests through.
+//!
+//! By default requests are sent with reqwest, through `ReqwestTransport`. Any other HTTP stack
+//! can be plugged in with `ExaBuilder::transport` by implementing `HttpTransport`, e.g. to add
+//! instrumentation, record traffic or serve canned responses in tests.
+
+use std::{error::Error, fmt, time::Duration};
+
+use bytes::Bytes;
+use futures::{
+    future::{self, BoxFuture},
+    stream::{self, BoxStream},
+    FutureExt, Stream, StreamExt, TryStreamExt,
+};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method, StatusCode,
+};
+use serde::Serialize;
+
+use crate::API_KEY_HEADER;
+
+/// Sends the HTTP requests built by `Exa`.
+///
+/// Retries, rate limiting and error classification are handled by `Exa`, so a transport only
+/// has to send each request once and hand back whatever the server answered, whatever its
+/// status. It should only fail when no response could be obtained at all.
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>>;
+}
+
+/// An HTTP request ready to be sent
+///
+/// Its `Debug` output redacts the API key, so requests can be logged as is.
+#[derive(Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// Absolute URL, including the query string
+    pub url: String,
+    pub headers: HeaderMap,
+    /// JSON body, if the request has one
+    pub body: Option<Bytes>,
+}
+
+impl HttpRequest {
+    /// Parses the body as JSON, if there is one.
+    pub fn json_body(&self) -> serde_json::Result<Option<serde_json::Value>> {
+        self.body
+            .as_ref()
+            .map(|body| serde_json::from_slice(body))
+            .transpose()
+    }
+
+    /// Replaces the body with `body` serialized as JSON.
+    pub fn set_json_body<S>(&mut self, body: &S) -> serde_json::Result<()>
+    where
+        S: Serialize + ?Sized,
+    {
+        self.body = Some(serde_json::to_vec(body)?.into());
+        Ok(())
+    }
+
+    /// Returns the headers with the API key replaced by `[REDACTED]`.
+    pub fn redacted_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        if headers.contains_key(API_KEY_HEADER) {
+            headers.insert(API_KEY_HEADER, HeaderValue::from_static("[REDACTED]"));
+        }
+        headers
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &self.redacted_headers())
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// An HTTP response, with a body that's streamed so server-sent events can be read as they
+/// arrive
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: BoxStream<'static, Result<Bytes, TransportError>>,
+}
+
+impl HttpResponse {
+    /// Creates a response whose body is already fully available.
+    pub fn new(status: StatusCode, headers: HeaderMap, body: impl Into<Bytes>) -> Self {
+        Self::from_stream(status, headers, stream::once(future::ok(body.into())))
+    }
+
+    /// Creates a response whose body is read from `body` as it's consumed.
+    pub fn from_stream<S>(status: StatusCode, headers: HeaderMap, body: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, TransportError>> + Send + 'static,
+    {
+        Self {
+            status,
+            headers,
+            body: body.boxed(),
+        }
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes(self) -> Result<Bytes, TransportError> {
+        let chunks = self.body.try_collect::<Vec<_>>().await?;
+        Ok(chunks.concat().into())
+    }
+
+    /// Reads the whole body as text, replacing any invalid UTF-8.
+    pub async fn text(self) -> Result<String, TransportError> {
+        let bytes = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The default transport, sending requests with a `reqwest::Client`
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        async move {
+            let response = builder.send().await?;
+            Ok(HttpResponse::from_stream(
+                response.status(),
+                response.headers().clone(),
+                response.bytes_stream().map_err(TransportError::from),
+            ))
+        }
+        .boxed()
+    }
+}
+
+/// Which HTTP version the default transport speaks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HttpVersionPreference {
+    /// Negotiate the version with the server, preferring HTTP/2 over TLS when available
+    #[default]
+    Negotiate,
+    /// Only use HTTP/1.1
+    Http1Only,
+    /// Assume the server speaks HTTP/2, without negotiating
+    Http2PriorKnowledge,
+}
+
+/// Settings of the `reqwest::Client` built for the default transport
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ClientOptions {
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub timeout: Option<Duration>,
+    pub proxy: Option<String>,
+    pub gzip: Option<bool>,
+    pub brotli: Option<bool>,
+    pub http_version: HttpVersionPreference,
+}
+
+impl ClientOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn build(&self) -> reqwest::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(enable) = self.gzip {
+            builder = builder.gzip(enable);
+        }
+        if let Some(enable) = self.brotli {
+            builder = builder.brotli(enable);
+        }
+        builder = match self.http_version {
+            HttpVersionPreference::Negotiate => builder,
+            HttpVersionPreference::Http1Only => builder.http1_only(),
+            HttpVersionPreference::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+        };
+        builder.build()
+    }
+
+    /// Builds the client of the blocking transport, which has no notion of read timeout.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(&self) -> reqwest::Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(enable) = self.gzip {
+            builder = builder.gzip(enable);
+        }
+        if let Some(enable) = self.brotli {
+            builder = builder.brotli(enable);
+        }
+        builder = match self.http_version {
+            HttpVersionPreference::Negotiate => builder,
+            HttpVersionPreference::Http1Only => builder.http1_only(),
+            HttpVersionPreference::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+        };
+        builder.build()
+    }
+}
+
+/// Error raised when no response could be obtained from the server
+#[derive(Debug, thiserror::Error)]
+#[error("{source}")]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    #[source]
+    source: Box<dyn Error + Send + Sync>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    /// The request couldn't be built, e.g. because its body failed to serialize
+    Builder,
+    /// The connection to the server couldn't be established
+    Connect,
+    /// The server didn't answer in time
+    Timeout,
+    /// The request failed while being sent
+    Request,
+    /// The response body couldn't be read
+    Body,
+    Other,
+}
+
+impl TransportError {
+    pub fn new(kind: TransportErrorKind, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    pub fn is_connect(&self) -> bool {
+        self.kind == TransportErrorKind::Connect
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.kind == TransportErrorKind::Timeout
+    }
+
+    pub fn is_request(&self) -> bool {
+        self.kind == TransportErrorKind::Request
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(error: reqwest::Error) -> Self {
+        let kind = if error.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if error.is_connect() {
+            TransportErrorKind::Connect
+        } else if error.is_builder() {
+            TransportErrorKind::Builder
+        } else if error.is_body() || error.is_decode() {
+            TransportErrorKind::Body
+        } else if error.is_request() {
+            TransportErrorKind::Request
+        } else {
+            TransportErrorKind::Other
+        };
+
+        Self::new(kind, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Exa, ExaError, HttpVersionPreference, ListParams, RetryPolicy, SearchRequest};
+
+    /// Transport answering with canned responses and recording the requests it receives
+    #[derive(Default)]
+    struct FakeTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+        responses: Mutex<VecDeque<Result<HttpResponse, TransportError>>>,
+    }
+
+    impl FakeTransport {
+        fn respond(&self, status: u16, body: serde_json::Value) {
+            self.responses
+                .lock()
+                .unwrap()
+                .push_back(Ok(HttpResponse::new(
+                    StatusCode::from_u16(status).unwrap(),
+                    HeaderMap::new(),
+                    body.to_string(),
+                )));
+        }
+
+        fn fail(&self, kind: TransportErrorKind) {
+            self.responses
+                .lock()
+                .unwrap()
+                .push_back(Err(TransportError::new(kind, "connection reset")));
+        }
+    }
+
+    impl HttpTransport for Arc<FakeTransport> {
+        fn send(
+            &self,
+            request: HttpRequest,
+        ) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+            self.requests.lock().unwrap().push(request);
+            let response = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("unexpected request");
+            future::ready(response).boxed()
+        }
+    }
+
+    fn setup(transport: &Arc<FakeTransport>) -> Result<Exa> {
+        Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url("https://exa.test".to_string())
+            .transport(transport.clone())
+            .retry_policy(RetryPolicy {
+                base_delay: Duration::from_millis(1),
+                jitter: false,
+                ..Default::default()
+            })
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_the_transport() -> Result<()> {
+        let transport = Arc::new(FakeTransport::default());
+        transport.respond(200, json!({"results": []}));
+        transport.respond(
+            200,
+            json!({"data": [], "hasMore": false, "nextCursor": null}),
+        );
+
+        let exa = setup(&transport)?;
+
+        exa.search(SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        })
+        .await?;
+        exa.research()
+            .list(ListParams {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .await?;
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests[0].method, Method::POST);
+        assert_eq!(requests[0].url, "https://exa.test/search");
+        assert_eq!(requests[0].headers["x-api-key"], "test_key");
+        assert_eq!(requests[0].headers["content-type"], "application/json");
+        let body = serde_json::from_slice::<serde_json::Value>(requests[0].body.as_ref().unwrap())?;
+        assert_eq!(body["query"], "test query");
+
+        assert_eq!(requests[1].method, Method::GET);
+        assert_eq!(requests[1].url, "https://exa.test/research/v1?limit=2");
+        assert!(requests[1].body.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_are_retried_and_classified() -> Result<()> {
+        let transport = Arc::new(FakeTransport::default());
+        transport.fail(TransportErrorKind::Connect);
+        transport.respond(200, json!({"results": []}));
+        transport.fail(TransportErrorKind::Other);
+
+        let exa = setup(&transport)?;
+        let request = || SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        };
+
+        assert!(exa.search(request()).await.is_ok());
+
+        let result = exa.search(request()).await;
+        match result {
+            Err(ExaError::ClientError(error)) => {
+                assert_eq!(error.kind(), TransportErrorKind::Other)
+            }
+            other => panic!("Expected ClientError, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(transport.requests.lock().unwrap().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_and_trailing_slash() -> Result<()> {
+        let transport = Arc::new(FakeTransport::default());
+        transport.respond(200, json!({"results": []}));
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url("https://exa.test/".to_string())
+            .user_agent_suffix("my-app/1.0")
+            .transport(transport.clone())
+            .build()?;
+
+        exa.search(SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://exa.test/search");
+        assert_eq!(
+            requests[0].headers["user-agent"],
+            format!("{} my-app/1.0", crate::DEFAULT_USER_AGENT).as_str()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let builder = || Exa::builder().api_key("test_key".to_string());
+
+        assert!(builder().base_url("not a url".to_string()).build().is_err());
+        assert!(builder()
+            .base_url("ftp://exa.test".to_string())
+            .build()
+            .is_err());
+        assert!(builder().proxy("not a proxy url").build().is_err());
+        assert!(builder()
+            .http_client(reqwest::Client::new())
+            .timeout(Duration::from_secs(5))
+            .build()
+            .is_err());
+        assert!(builder()
+            .http_client(reqwest::Client::new())
+            .transport(ReqwestTransport::default())
+            .build()
+            .is_err());
+        assert!(builder()
+            .base_url("http://localhost:8080".to_string())
+            .connect_timeout(Duration::from_secs(1))
+            .read_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(10))
+            .gzip(false)
+            .brotli(false)
+            .http_version(HttpVersionPreference::Http1Only)
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_caller_supplied_http_client() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+
+        let m = server
+            .mock("POST", "/search")
+            .match_header("x-api-key", "test_key")
+            .with_status(200)
+            .with_body(json!({"results": []}).to_string())
+            .create();
+
+        let exa = Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(server.url())
+            .http_client(reqwest::Client::builder().build()?)
+            .build()?;
+
+        exa.search(SearchRequest {
+            query: "test query".to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+        m.assert();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+//! Verification and parsing of the webhook events Exa sends for websets and research tasks.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use sha2::Sha256;
+
+use crate::{
+    research::ResearchTask,
+    websets::{Webset, WebsetItem, WebsetSearch},
+};
+
+/// Header carrying the signature of a webhook request
+pub const SIGNATURE_HEADER: &str = "exa-signature";
+
+/// Default maximum age of a webhook request accepted by `verify_signature`
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Missing {SIGNATURE_HEADER} header")]
+    MissingSignature,
+    #[error("Malformed {SIGNATURE_HEADER} header")]
+    MalformedSignature,
+    #[error("Signature doesn't match the payload")]
+    InvalidSignature,
+    #[error("Timestamp is outside the tolerance window")]
+    TimestampOutOfTolerance,
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+/// Verifies that a webhook request was sent by Exa.
+///
+/// The `Exa-Signature` header has the form `t=<unix timestamp>,v1=<hex signature>`, where the
+/// signature is the HMAC-SHA256 of `<timestamp>.<body>` keyed with the webhook secret. Requests
+/// whose timestamp is further than `tolerance` from the current time are rejected to prevent
+/// replay attacks.
+///
+/// `body` must be the raw request body, before any JSON parsing.
+pub fn verify_signature(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+) -> Result<(), WebhookError> {
+    verify_signature_at(secret, headers, body, tolerance, SystemTime::now())
+}
+
+/// Verifies the signature of a webhook request and parses its payload.
+pub fn construct_event(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+) -> Result<WebhookEvent, WebhookError> {
+    verify_signature(secret, headers, body, tolerance)?;
+    Ok(serde_json::from_slice(body)?)
+}
+
+fn verify_signature_at(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+    now: SystemTime,
+) -> Result<(), WebhookError> {
+    let header = headers
+        .get(SIGNATURE_HEADER)
+        .ok_or(WebhookError::MissingSignature)?
+        .to_str()
+        .map_err(|_| WebhookError::MalformedSignature)?;
+
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<u64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or(WebhookError::MalformedSignature)?;
+    if signatures.is_empty() {
+        return Err(WebhookError::MalformedSignature);
+    }
+
+    let now = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    if now.abs_diff(timestamp) > tolerance.as_secs() {
+        return Err(WebhookError::TimestampOutOfTolerance);
+    }
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    let valid = signatures.into_iter().any(|signature| {
+        hex::decode(signature)
+            .map(|signature| mac.clone().verify_slice(&signature).is_ok())
+            .unwrap_or(false)
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(WebhookError::InvalidSignature)
+    }
+}
+
+/// Represents an event delivered to a webhook
+#[derive(Debug, serde::Deserialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(flatten)]
+    pub kind: WebhookEventKind,
+}
+
+/// The type of a webhook event, along with the object it's about
+#[derive(Debug, serde::Deserialize)]
+#[serde(try_from = "RawWebhookEventKind")]
+pub enum WebhookEventKind {
+    WebsetCreated(Webset),
+    WebsetDeleted(Webset),
+    WebsetPaused(Webset),
+    WebsetIdle(Webset),
+    WebsetSearchCreated(WebsetSearch),
+    WebsetSearchUpdated(WebsetSearch),
+    WebsetSearchCompleted(WebsetSearch),
+    WebsetSearchCanceled(WebsetSearch),
+    WebsetItemCreated(WebsetItem),
+    WebsetItemEnriched(WebsetItem),
+    ResearchCompleted(ResearchTask),
+    ResearchFailed(ResearchTask),
+    /// An event type this version of the SDK doesn't know about
+    Unknown {
+        event_type: String,
+        data: serde_json::Value,
+    },
+}
+
+#[derive(serde::Deserialize)]
+struct RawWebhookEventKind {
+    #[serde(rename = "type")]
+    event_type: String,
+    data: serde_json::Value,
+}
+
+impl TryFrom<RawWebhookEventKind> for WebhookEventKind {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawWebhookEventKind) -> Result<Self, Self::Error> {
+        use serde_json::from_value;
+
+        Ok(match raw.event_type.as_str() {
+            "webset.created" => WebhookEventKind::WebsetCreated(from_value(raw.data)?),
+            "webset.deleted" => WebhookEventKind::WebsetDeleted(from_value(raw.data)?),
+            "webset.paused" => WebhookEventKind::WebsetPaused(from_value(raw.data)?),
+            "webset.idle" => WebhookEventKind::WebsetIdle(from_value(raw.data)?),
+            "webset.search.created" => WebhookEventKind::WebsetSearchCreated(from_value(raw.data)?),
+            "webset.search.updated" => WebhookEventKind::WebsetSearchUpdated(from_value(raw.data)?),
+            "webset.search.completed" => {
+                WebhookEventKind::WebsetSearchCompleted(from_value(raw.data)?)
+            }
+            "webset.search.canceled" => {
+                WebhookEventKind::WebsetSearchCanceled(from_value(raw.data)?)
+            }
+            "webset.item.created" => WebhookEventKind::WebsetItemCreated(from_value(raw.data)?),
+            "webset.item.enriched" => WebhookEventKind::WebsetItemEnriched(from_value(raw.data)?),
+            "research.completed" => WebhookEventKind::ResearchCompleted(from_value(raw.data)?),
+            "research.failed" => WebhookEventKind::ResearchFailed(from_value(raw.data)?),
+            _ => WebhookEventKind::Unknown {
+                event_type: raw.event_type,
+                data: raw.data,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    fn sign(timestamp: u64, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn item_event() -> Vec<u8> {
+        json!({
+            "id": "evt_1",
+            "object": "event",
+            "type": "webset.item.created",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "data": {
+                "id": "i_1",
+                "object": "webset_item",
+                "websetId": "ws_1",
+                "properties": {"type": "company", "url": "https://example.com"},
+                "createdAt": "2024-01-01T00:00:00Z",
+                "updatedAt": "2024-01-01T00:00:00Z"
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = item_event();
+        let headers = headers(&format!("t={},v1={}", TIMESTAMP, sign(TIMESTAMP, &body)));
+
+        assert!(verify_signature_at(
+            SECRET,
+            &headers,
+            &body,
+            DEFAULT_TOLERANCE,
+            at(TIMESTAMP + 10)
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_signature_at("other", &headers, &body, DEFAULT_TOLERANCE, at(TIMESTAMP)),
+            Err(WebhookError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_signature_at(SECRET, &headers, b"{}", DEFAULT_TOLERANCE, at(TIMESTAMP)),
+            Err(WebhookError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_signature_at(
+                SECRET,
+                &headers,
+                &body,
+                DEFAULT_TOLERANCE,
+                at(TIMESTAMP + 301)
+            ),
+            Err(WebhookError::TimestampOutOfTolerance)
+        ));
+    }
+
+    #[test]
+    fn test_verify_signature_accepts_any_of_several_signatures() {
+        let body = item_event();
+        let headers = headers(&format!(
+            "t={}, v1=deadbeef, v1={}",
+            TIMESTAMP,
+            sign(TIMESTAMP, &body)
+        ));
+
+        assert!(
+            verify_signature_at(SECRET, &headers, &body, DEFAULT_TOLERANCE, at(TIMESTAMP)).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_malformed_header() {
+        let body = item_event();
+
+        assert!(matches!(
+            verify_signature_at(
+                SECRET,
+                &HeaderMap::new(),
+                &body,
+                DEFAULT_TOLERANCE,
+                at(TIMESTAMP)
+            ),
+            Err(WebhookError::MissingSignature)
+        ));
+        assert!(matches!(
+            verify_signature_at(
+                SECRET,
+                &headers("v1=deadbeef"),
+                &body,
+                DEFAULT_TOLERANCE,
+                at(TIMESTAMP)
+            ),
+            Err(WebhookError::MalformedSignature)
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_webhook_events() -> Result<(), serde_json::Error> {
+        let event = serde_json::from_slice::<WebhookEvent>(&item_event())?;
+
+        assert_eq!(event.id, "evt_1");
+        match event.kind {
+            WebhookEventKind::WebsetItemCreated(item) => assert_eq!(item.id, "i_1"),
+            other => panic!("Expected WebsetItemCreated, got {:?}", other),
+        }
+
+        let event = serde_json::from_value::<WebhookEvent>(json!({
+            "id": "evt_2",
+            "type": "webset.export.created",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "data": {"id": "x_1"}
+        }))?;
+
+        match event.kind {
+            WebhookEventKind::Unknown { event_type, data } => {
+                assert_eq!(event_type, "webset.export.created");
+                assert_eq!(data, json!({"id": "x_1"}));
+            }
+            other => panic!("Expected Unknown, got {:?}", other),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,872 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use futures::Stream;
+
+use crate::{
+    pagination::{paginate, ListParams, Page},
+    path_segment, Exa, ExaError,
+};
+
+const WEBSETS_PATH: &str = "/websets/v0/websets";
+
+impl Exa {
+    /// Returns a client for Exa Websets.
+    pub fn websets(&self) -> Websets<'_> {
+        Websets { exa: self }
+    }
+}
+
+/// Client for the `/websets/v0` endpoints, obtained through `Exa::websets`.
+///
+/// A webset is a long-lived collection of items found by a search and verified against its
+/// criteria. Items keep being added while the webset is running, and enrichments extract
+/// additional data from each of them.
+pub struct Websets<'a> {
+    exa: &'a Exa,
+}
+
+impl<'a> Websets<'a> {
+    /// Creates a webset and starts its initial search.
+    ///
+    /// Server errors aren't retried, since the webset may have been created anyway; see
+    /// `RetryPolicy`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use exa_sdk::{CreateWebsetRequest, Exa, WebsetCriterion, WebsetSearchRequest};
+    /// # use anyhow::Result;
+    /// #
+    /// # async fn example() -> Result<()> {
+    /// # let exa = Exa::builder().api_key("your_api_key".into()).build()?;
+    /// let webset = exa
+    ///     .websets()
+    ///     .create(CreateWebsetRequest {
+    ///         search: WebsetSearchRequest {
+    ///             query: "Seed-stage developer tooling startups".to_string(),
+    ///             count: Some(50),
+    ///             criteria: vec![WebsetCriterion::new("Founded after 2020")],
+    ///             ..Default::default()
+    ///         },
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// println!("Created webset {}", webset.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create(&self, request: CreateWebsetRequest) -> Result<Webset, ExaError> {
+        self.exa.post_non_idempotent(WEBSETS_PATH, request).await
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Webset, ExaError> {
+        self.exa
+            .get(format!("{}/{}", WEBSETS_PATH, path_segment(id)), &())
+            .await
+    }
+
+    /// Lists websets, one page at a time.
+    pub async fn list(&self, params: ListParams) -> Result<Page<Webset>, ExaError> {
+        self.exa.get(WEBSETS_PATH, &params).await
+    }
+
+    /// Lists all websets, fetching pages of `limit` items as the stream is consumed.
+    pub fn list_all(
+        &self,
+        limit: Option<u32>,
+    ) -> impl Stream<Item = Result<Webset, ExaError>> + 'a {
+        let exa = self.exa;
+        paginate(limit, move |params| async move {
+            exa.websets().list(params).await
+        })
+    }
+
+    pub async fn update(&self, id: &str, request: UpdateWebsetRequest) -> Result<Webset, ExaError> {
+        self.exa
+            .patch(format!("{}/{}", WEBSETS_PATH, path_segment(id)), request)
+            .await
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<Webset, ExaError> {
+        self.exa
+            .delete(format!("{}/{}", WEBSETS_PATH, path_segment(id)))
+            .await
+    }
+
+    /// Cancels the searches and enrichments currently running on a webset.
+    pub async fn cancel(&self, id: &str) -> Result<Webset, ExaError> {
+        self.exa
+            .post(
+                format!("{}/{}/cancel", WEBSETS_PATH, path_segment(id)),
+                EmptyRequest {},
+            )
+            .await
+    }
+
+    /// Lists the items of a webset, one page at a time.
+    pub async fn list_items(
+        &self,
+        webset_id: &str,
+        params: ListParams,
+    ) -> Result<Page<WebsetItem>, ExaError> {
+        self.exa
+            .get(
+                format!("{}/{}/items", WEBSETS_PATH, path_segment(webset_id)),
+                &params,
+            )
+            .await
+    }
+
+    /// Lists all the items of a webset, fetching pages of `limit` items as the stream is
+    /// consumed.
+    pub fn list_all_items(
+        &self,
+        webset_id: &str,
+        limit: Option<u32>,
+    ) -> impl Stream<Item = Result<WebsetItem, ExaError>> + 'a {
+        let exa = self.exa;
+        let webset_id = webset_id.to_string();
+        paginate(limit, move |params| {
+            let webset_id = webset_id.clone();
+            async move { exa.websets().list_items(&webset_id, params).await }
+        })
+    }
+
+    pub async fn get_item(&self, webset_id: &str, item_id: &str) -> Result<WebsetItem, ExaError> {
+        self.exa
+            .get(
+                format!(
+                    "{}/{}/items/{}",
+                    WEBSETS_PATH,
+                    path_segment(webset_id),
+                    path_segment(item_id)
+                ),
+                &(),
+            )
+            .await
+    }
+
+    pub async fn delete_item(
+        &self,
+        webset_id: &str,
+        item_id: &str,
+    ) -> Result<WebsetItem, ExaError> {
+        self.exa
+            .delete(format!(
+                "{}/{}/items/{}",
+                WEBSETS_PATH,
+                path_segment(webset_id),
+                path_segment(item_id)
+            ))
+            .await
+    }
+
+    /// Adds an enrichment to a webset, which runs on every existing and future item.
+    ///
+    /// Like `create`, server errors aren't retried.
+    pub async fn create_enrichment(
+        &self,
+        webset_id: &str,
+        request: CreateEnrichmentRequest,
+    ) -> Result<WebsetEnrichment, ExaError> {
+        self.exa
+            .post_non_idempotent(
+                format!("{}/{}/enrichments", WEBSETS_PATH, path_segment(webset_id)),
+                request,
+            )
+            .await
+    }
+
+    pub async fn get_enrichment(
+        &self,
+        webset_id: &str,
+        enrichment_id: &str,
+    ) -> Result<WebsetEnrichment, ExaError> {
+        self.exa
+            .get(
+                format!(
+                    "{}/{}/enrichments/{}",
+                    WEBSETS_PATH,
+                    path_segment(webset_id),
+                    path_segment(enrichment_id)
+                ),
+                &(),
+            )
+            .await
+    }
+
+    pub async fn delete_enrichment(
+        &self,
+        webset_id: &str,
+        enrichment_id: &str,
+    ) -> Result<WebsetEnrichment, ExaError> {
+        self.exa
+            .delete(format!(
+                "{}/{}/enrichments/{}",
+                WEBSETS_PATH,
+                path_segment(webset_id),
+                path_segment(enrichment_id)
+            ))
+            .await
+    }
+
+    pub async fn cancel_enrichment(
+        &self,
+        webset_id: &str,
+        enrichment_id: &str,
+    ) -> Result<WebsetEnrichment, ExaError> {
+        self.exa
+            .post(
+                format!(
+                    "{}/{}/enrichments/{}/cancel",
+                    WEBSETS_PATH,
+                    path_segment(webset_id),
+                    path_segment(enrichment_id)
+                ),
+                EmptyRequest {},
+            )
+            .await
+    }
+}
+
+#[derive(serde::Serialize)]
+struct EmptyRequest {}
+
+/// Represents a request to create a webset
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct CreateWebsetRequest {
+    /// The initial search populating the webset
+    pub search: WebsetSearchRequest,
+    /// Enrichments to run on every item
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub enrichments: Vec<CreateEnrichmentRequest>,
+    /// Identifier of the webset in your own system
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalId")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct WebsetSearchRequest {
+    /// Natural language description of the items to find
+    pub query: String,
+    /// Number of items to find
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// Kind of entity to find. Inferred from the query when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<WebsetEntity>,
+    /// Criteria every item must satisfy. Inferred from the query when omitted
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub criteria: Vec<WebsetCriterion>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetCriterion {
+    pub description: String,
+    /// Share of the evaluated items satisfying this criterion, reported by the API
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "successRate",
+        default
+    )]
+    pub success_rate: Option<f64>,
+}
+
+impl WebsetCriterion {
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            success_rate: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetEntity {
+    #[serde(rename = "type")]
+    pub kind: WebsetEntityKind,
+    /// Description of the entity, for `WebsetEntityKind::Custom`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebsetEntityKind {
+    Company,
+    Person,
+    Article,
+    ResearchPaper,
+    Custom,
+    /// An entity type not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct UpdateWebsetRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Represents a request to add an enrichment to a webset
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct CreateEnrichmentRequest {
+    /// What to extract from each item
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<EnrichmentFormat>,
+    /// Allowed values, for `EnrichmentFormat::Options`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub options: Vec<EnrichmentOption>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnrichmentFormat {
+    Text,
+    Date,
+    Number,
+    Options,
+    Email,
+    Phone,
+    Url,
+    /// A format not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct EnrichmentOption {
+    pub label: String,
+}
+
+/// Represents a webset
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Webset {
+    pub id: String,
+    pub status: WebsetStatus,
+    #[serde(rename = "externalId")]
+    pub external_id: Option<String>,
+    #[serde(default)]
+    pub searches: Vec<WebsetSearch>,
+    #[serde(default)]
+    pub enrichments: Vec<WebsetEnrichment>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebsetStatus {
+    Idle,
+    Pending,
+    Running,
+    Paused,
+    /// A status not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+/// Represents a search populating a webset
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetSearch {
+    pub id: String,
+    pub status: WebsetSearchStatus,
+    pub query: String,
+    pub entity: Option<WebsetEntity>,
+    #[serde(default)]
+    pub criteria: Vec<WebsetCriterion>,
+    pub count: Option<u32>,
+    pub progress: Option<WebsetSearchProgress>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebsetSearchStatus {
+    Created,
+    Pending,
+    Running,
+    Completed,
+    Canceled,
+    /// A status not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetSearchProgress {
+    /// Number of items found so far
+    pub found: u32,
+    /// Completion percentage of the search
+    pub completion: f64,
+}
+
+/// Represents an enrichment configured on a webset
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetEnrichment {
+    pub id: String,
+    pub status: EnrichmentStatus,
+    #[serde(rename = "websetId")]
+    pub webset_id: String,
+    pub title: Option<String>,
+    pub description: String,
+    pub format: Option<EnrichmentFormat>,
+    #[serde(default)]
+    pub options: Vec<EnrichmentOption>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnrichmentStatus {
+    Pending,
+    Canceled,
+    Completed,
+    /// A status not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+/// Represents an item found by a webset search
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetItem {
+    pub id: String,
+    #[serde(rename = "websetId")]
+    pub webset_id: String,
+    pub source: Option<String>,
+    #[serde(rename = "sourceId")]
+    pub source_id: Option<String>,
+    pub properties: WebsetItemProperties,
+    /// How the item scored against each of the webset criteria
+    #[serde(default)]
+    pub evaluations: Vec<WebsetItemEvaluation>,
+    /// Results of the enrichments run on the item
+    #[serde(default)]
+    pub enrichments: Vec<EnrichmentResult>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetItemProperties {
+    /// Kind of entity, e.g. `company` or `person`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    /// Entity specific properties, such as the `company` or `person` objects
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetItemEvaluation {
+    pub criterion: String,
+    pub reasoning: Option<String>,
+    pub satisfied: CriterionSatisfied,
+    #[serde(default)]
+    pub references: Vec<WebsetReference>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CriterionSatisfied {
+    Yes,
+    No,
+    Unclear,
+    /// A value not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebsetReference {
+    pub title: Option<String>,
+    pub snippet: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct EnrichmentResult {
+    #[serde(rename = "enrichmentId")]
+    pub enrichment_id: String,
+    pub format: Option<EnrichmentFormat>,
+    /// Extracted values, if the enrichment found any
+    pub result: Option<Vec<String>>,
+    pub reasoning: Option<String>,
+    #[serde(default)]
+    pub references: Vec<WebsetReference>,
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use futures::TryStreamExt;
+    use mockito::{Matcher, Server as MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn setup(base_url: String) -> Result<Exa> {
+        Exa::builder()
+            .api_key("test_key".to_string())
+            .base_url(base_url)
+            .build()
+    }
+
+    fn webset(status: &str) -> serde_json::Value {
+        json!({
+            "id": "ws_1",
+            "object": "webset",
+            "status": status,
+            "externalId": null,
+            "searches": [{
+                "id": "s_1",
+                "status": "running",
+                "query": "Test query",
+                "entity": {"type": "company"},
+                "criteria": [{"description": "Test criterion", "successRate": 0.5}],
+                "count": 10,
+                "progress": {"found": 3, "completion": 30.0}
+            }],
+            "enrichments": [],
+            "metadata": {},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    fn item(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "object": "webset_item",
+            "source": "search",
+            "sourceId": "s_1",
+            "websetId": "ws_1",
+            "properties": {
+                "type": "company",
+                "url": "https://example.com",
+                "description": "Test description",
+                "company": {"name": "Example"}
+            },
+            "evaluations": [{
+                "criterion": "Test criterion",
+                "reasoning": "Test reasoning",
+                "satisfied": "yes",
+                "references": []
+            }],
+            "enrichments": [],
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_webset() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/websets/v0/websets")
+            .match_body(Matcher::Json(json!({
+                "search": {
+                    "query": "Test query",
+                    "count": 10,
+                    "entity": {"type": "company"},
+                    "criteria": [{"description": "Test criterion"}]
+                },
+                "enrichments": [{"description": "CEO name", "format": "text"}],
+                "externalId": "ext_1"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(webset("running").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let webset = exa
+            .websets()
+            .create(CreateWebsetRequest {
+                search: WebsetSearchRequest {
+                    query: "Test query".to_string(),
+                    count: Some(10),
+                    entity: Some(WebsetEntity {
+                        kind: WebsetEntityKind::Company,
+                        description: None,
+                    }),
+                    criteria: vec![WebsetCriterion::new("Test criterion")],
+                },
+                enrichments: vec![CreateEnrichmentRequest {
+                    description: "CEO name".to_string(),
+                    format: Some(EnrichmentFormat::Text),
+                    ..Default::default()
+                }],
+                external_id: Some("ext_1".to_string()),
+                metadata: None,
+            })
+            .await?;
+
+        assert_eq!(webset.id, "ws_1");
+        assert_eq!(webset.status, WebsetStatus::Running);
+        assert_eq!(webset.searches[0].criteria[0].success_rate, Some(0.5));
+        assert_eq!(webset.searches[0].progress.as_ref().unwrap().found, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decodes_pending_and_unknown_statuses() -> Result<()> {
+        let pending: Webset = serde_json::from_value(webset("pending"))?;
+        assert_eq!(pending.status, WebsetStatus::Pending);
+
+        let mut value = webset("archived");
+        value["searches"][0]["status"] = json!("queued");
+        value["searches"][0]["entity"]["type"] = json!("product");
+        value["enrichments"] = json!([{
+            "id": "we_1",
+            "status": "failed",
+            "websetId": "ws_1",
+            "title": null,
+            "description": "Test enrichment",
+            "format": "currency",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }]);
+        let unknown: Webset = serde_json::from_value(value)?;
+        assert_eq!(unknown.status, WebsetStatus::Unknown);
+        assert_eq!(unknown.searches[0].status, WebsetSearchStatus::Unknown);
+        assert_eq!(
+            unknown.searches[0].entity.as_ref().unwrap().kind,
+            WebsetEntityKind::Unknown
+        );
+        assert_eq!(unknown.enrichments[0].status, EnrichmentStatus::Unknown);
+        assert_eq!(
+            unknown.enrichments[0].format,
+            Some(EnrichmentFormat::Unknown)
+        );
+
+        let mut value = item("it_1");
+        value["evaluations"][0]["satisfied"] = json!("partially");
+        let item: WebsetItem = serde_json::from_value(value)?;
+        assert_eq!(item.evaluations[0].satisfied, CriterionSatisfied::Unknown);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_delete_and_cancel_webset() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let update = server
+            .mock("PATCH", "/websets/v0/websets/ws_1")
+            .match_body(Matcher::Json(json!({"metadata": {"team": "sales"}})))
+            .with_status(200)
+            .with_body(webset("idle").to_string())
+            .create();
+        let cancel = server
+            .mock("POST", "/websets/v0/websets/ws_1/cancel")
+            .with_status(200)
+            .with_body(webset("idle").to_string())
+            .create();
+        let delete = server
+            .mock("DELETE", "/websets/v0/websets/ws_1")
+            .with_status(200)
+            .with_body(webset("idle").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+        let websets = exa.websets();
+
+        websets
+            .update(
+                "ws_1",
+                UpdateWebsetRequest {
+                    metadata: Some(HashMap::from([("team".to_string(), "sales".to_string())])),
+                },
+            )
+            .await?;
+        websets.cancel("ws_1").await?;
+        let deleted = websets.delete("ws_1").await?;
+
+        update.assert();
+        cancel.assert();
+        delete.assert();
+        assert_eq!(deleted.status, WebsetStatus::Idle);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ids_are_escaped_in_paths() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let m = server
+            .mock(
+                "GET",
+                "/websets/v0/websets/ws_1%2Fitems/items/it%3Fx%3D1%231",
+            )
+            .with_status(200)
+            .with_body(item("it_1").to_string())
+            .create();
+
+        let exa = setup(server.url())?;
+
+        exa.websets().get_item("ws_1/items", "it?x=1#1").await?;
+
+        m.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_all_items_follows_cursor() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _first = server
+            .mock("GET", "/websets/v0/websets/ws_1/items")
+            .match_query(Matcher::Missing)
+            .with_status(200)
+            .with_body(
+                json!({"data": [item("i_1"), item("i_2")], "hasMore": true, "nextCursor": "c_1"})
+                    .to_string(),
+            )
+            .create();
+        let _second = server
+            .mock("GET", "/websets/v0/websets/ws_1/items")
+            .match_query(Matcher::UrlEncoded("cursor".into(), "c_1".into()))
+            .with_status(200)
+            .with_body(
+                json!({"data": [item("i_3")], "hasMore": false, "nextCursor": null}).to_string(),
+            )
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let items = exa
+            .websets()
+            .list_all_items("ws_1", None)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["i_1", "i_2", "i_3"]
+        );
+        assert_eq!(items[0].properties.kind, "company");
+        assert_eq!(items[0].properties.extra["company"]["name"], "Example");
+        assert_eq!(items[0].evaluations[0].satisfied, CriterionSatisfied::Yes);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_creates_are_not_retried_on_server_errors() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let webset = server
+            .mock("POST", "/websets/v0/websets")
+            .with_status(503)
+            .expect(1)
+            .create();
+        let enrichment = server
+            .mock("POST", "/websets/v0/websets/ws_1/enrichments")
+            .with_status(500)
+            .expect(1)
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let result = exa.websets().create(CreateWebsetRequest::default()).await;
+        assert!(matches!(result, Err(ExaError::ServerError(_))));
+        let result = exa
+            .websets()
+            .create_enrichment("ws_1", CreateEnrichmentRequest::default())
+            .await;
+        assert!(matches!(result, Err(ExaError::ServerError(_))));
+
+        webset.assert();
+        enrichment.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_enrichment() -> Result<()> {
+        let mut server = MockServer::new_async().await;
+
+        let _m = server
+            .mock("POST", "/websets/v0/websets/ws_1/enrichments")
+            .match_body(Matcher::Json(json!({
+                "description": "Company size",
+                "format": "options",
+                "options": [{"label": "small"}, {"label": "large"}]
+            })))
+            .with_status(201)
+            .with_body(
+                json!({
+                    "id": "e_1",
+                    "object": "webset_enrichment",
+                    "status": "pending",
+                    "websetId": "ws_1",
+                    "title": null,
+                    "description": "Company size",
+                    "format": "options",
+                    "options": [{"label": "small"}, {"label": "large"}],
+                    "metadata": {},
+                    "createdAt": "2024-01-01T00:00:00Z",
+                    "updatedAt": "2024-01-01T00:00:00Z"
+                })
+                .to_string(),
+            )
+            .create();
+
+        let exa = setup(server.url())?;
+
+        let enrichment = exa
+            .websets()
+            .create_enrichment(
+                "ws_1",
+                CreateEnrichmentRequest {
+                    description: "Company size".to_string(),
+                    format: Some(EnrichmentFormat::Options),
+                    options: vec![
+                        EnrichmentOption {
+                            label: "small".to_string(),
+                        },
+                        EnrichmentOption {
+                            label: "large".to_string(),
+                        },
+                    ],
+                    metadata: None,
+                },
+            )
+            .await?;
+
+        assert_eq!(enrichment.id, "e_1");
+        assert_eq!(enrichment.status, EnrichmentStatus::Pending);
+        assert_eq!(enrichment.options.len(), 2);
+
+        Ok(())
+    }
+}